@@ -0,0 +1,160 @@
+//! Regenerates `data/policy_table.bin` in the format described in `src/policy_table.rs`. Must be
+//! run without the table it's replacing:
+//!
+//! ```sh
+//! cargo run --release -p cactpot_solver_core --no-default-features --features parallel \
+//!     --example generate_policy_table
+//! ```
+//!
+//! The `parallel` feature is optional, but spreads the solves across threads.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use cactpot_solver_core::{Board, BoardPosition, Recommendation, ValuedBoardPosition};
+
+const MAX_FILLS: usize = 3;
+
+/// Call `f` with every set of `k` cells, lexicographically
+fn for_each_position_set<F: FnMut(&[u8])>(k: usize, f: &mut F) {
+    fn go<F: FnMut(&[u8])>(k: usize, start: u8, acc: &mut Vec<u8>, f: &mut F) {
+        if acc.len() == k {
+            return f(acc);
+        }
+        for pos in start..9 {
+            acc.push(pos);
+            go(k, pos + 1, acc, f);
+            acc.pop();
+        }
+    }
+
+    go(k, 0, &mut Vec::with_capacity(k), f);
+}
+
+/// Call `f` with every arrangement of `k` distinct values, lexicographically
+fn for_each_value_arrangement<F: FnMut(&[u8])>(k: usize, f: &mut F) {
+    fn go<F: FnMut(&[u8])>(k: usize, acc: &mut Vec<u8>, f: &mut F) {
+        if acc.len() == k {
+            return f(acc);
+        }
+        for value in 1..=9 {
+            if !acc.contains(&value) {
+                acc.push(value);
+                go(k, acc, f);
+                acc.pop();
+            }
+        }
+    }
+
+    go(k, &mut Vec::with_capacity(k), f);
+}
+
+/// Every canonical board with 1 to [`MAX_FILLS`] fills
+fn canonical_boards() -> Vec<Board> {
+    let mut out = Vec::new();
+    for num_fills in 1..=MAX_FILLS {
+        for_each_position_set(num_fills, &mut |positions| {
+            for_each_value_arrangement(num_fills, &mut |values| {
+                let mut board = Board::default();
+                for (pos, value) in positions.iter().zip(values.iter()) {
+                    let pos = BoardPosition::from_index(*pos);
+                    board
+                        .fill(ValuedBoardPosition::from_pos(*value, pos))
+                        .unwrap();
+                }
+
+                if board.canonical().0 == board {
+                    out.push(board);
+                }
+            });
+        });
+    }
+
+    out
+}
+
+/// The board's cells read as a base 10 number, 0 for empty cells
+fn state_key(board: &Board) -> u32 {
+    (0..9).fold(0, |key, idx| {
+        let value = board
+            .find(BoardPosition::from_index(idx))
+            .map_or(0, |v| v.value());
+        key * 10 + value as u32
+    })
+}
+
+/// Payout totals of revealing each empty cell, as LEB128 varints
+fn encode_totals(board: &Board) -> Vec<u8> {
+    let rec = Recommendation::from_board(board).unwrap();
+    let mut out = Vec::new();
+    for idx in 0..9 {
+        let mut total = match rec.reveal_value(BoardPosition::from_index(idx)) {
+            Some(v) => v.total(),
+            None => continue,
+        };
+        loop {
+            let byte = (total & 0x7f) as u8;
+            total >>= 7;
+            if total == 0 {
+                out.push(byte);
+                break;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    out
+}
+
+/// Encoded totals of every board, keyed by state
+#[cfg(feature = "parallel")]
+fn solve_all(boards: &[Board]) -> BTreeMap<u32, Vec<u8>> {
+    use rayon::prelude::*;
+
+    boards
+        .par_iter()
+        .map(|board| (state_key(board), encode_totals(board)))
+        .collect()
+}
+
+/// Encoded totals of every board, keyed by state
+#[cfg(not(feature = "parallel"))]
+fn solve_all(boards: &[Board]) -> BTreeMap<u32, Vec<u8>> {
+    boards
+        .iter()
+        .map(|board| (state_key(board), encode_totals(board)))
+        .collect()
+}
+
+fn main() -> std::io::Result<()> {
+    if cfg!(feature = "precomputed") {
+        eprintln!("Run with --no-default-features to compute the table from scratch");
+        std::process::exit(1);
+    }
+
+    let boards = canonical_boards();
+    println!("Solving {} canonical boards", boards.len());
+
+    let states = solve_all(&boards);
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/policy_table.bin");
+    let mut out = BufWriter::new(File::create(&path)?);
+
+    out.write_all(&(states.len() as u32).to_le_bytes())?;
+    let mut offset = 0u32;
+    for (key, totals) in states.iter() {
+        out.write_all(&key.to_le_bytes())?;
+        out.write_all(&offset.to_le_bytes())?;
+        offset += totals.len() as u32;
+    }
+    for totals in states.values() {
+        out.write_all(totals)?;
+    }
+    out.flush()?;
+
+    println!("Written to {}", path.display());
+
+    Ok(())
+}
@@ -1,30 +1,107 @@
+use std::hash::{Hash, Hasher};
+
 use fill_failure::FillFailure;
+use transform::Transform;
 
+use super::board_position::valued_board_position::MAX_VALUE;
 use super::end_row::EndRow;
-use super::{AvailableSelectionIter, BoardPosition, ValuedBoardPosition};
+use super::{AvailableSelectionIter, BoardPosition, EndingIter, PayoutTable, ValuedBoardPosition};
 use smallvec::SmallVec;
 
 pub mod available_selection_iter;
+pub mod edit;
+pub mod ending_iter;
 pub mod fill_failure;
+pub(crate) mod history;
+pub mod notation;
+pub mod transform;
 
 const BOARD_CAPACITY: u8 = 9;
 
-#[derive(Clone, PartialEq, Debug, Eq)]
+/// The revealed cells. Boards revealing the same values in the same cells are equal regardless of
+/// the order they were revealed in.
+#[derive(Clone, Debug)]
 pub struct Board {
     fills: SmallVec<[ValuedBoardPosition; 9]>,
 }
 
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        self.cells() == other.cells()
+    }
+}
+
+impl Eq for Board {}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cells().hash(state);
+    }
+}
+
 impl Board {
     #[inline]
     pub fn clear_fills(&mut self) {
         self.fills.clear();
     }
 
+    /// Revealed cells in the order they were revealed
+    #[inline]
+    pub fn fills(&self) -> &[ValuedBoardPosition] {
+        &self.fills
+    }
+
+    /// Value of each cell ordered by [position index](BoardPosition::index), 0 if unrevealed
+    pub(crate) fn cells(&self) -> [u8; BOARD_CAPACITY as usize] {
+        let mut out = [0; BOARD_CAPACITY as usize];
+        for fill in self.fills.iter() {
+            out[fill.position().index() as usize] = fill.value();
+        }
+
+        out
+    }
+
+    /// The same board with every fill moved by the given transform
+    pub fn transformed(&self, transform: Transform) -> Board {
+        let fills = self
+            .fills
+            .iter()
+            .map(|f| ValuedBoardPosition::from_pos(f.value(), transform.apply(f.position())))
+            .collect();
+
+        Board { fills }
+    }
+
+    /// The representative of the board's symmetry class, along with the transform turning this
+    /// board into it. All 8 rotations & reflections of a board share the same canonical board.
+    ///
+    /// Recommendations for the canonical board can be mapped back with
+    /// [`Transform::inverse`], [`Transform::cells_from_transformed`] &
+    /// [`Transform::lines_from_transformed`].
+    pub fn canonical(&self) -> (Board, Transform) {
+        let mut best = Transform::Identity;
+        let mut best_cells = self.cells();
+        for t in Transform::ALL.iter().skip(1) {
+            let cells = self.transformed(*t).cells();
+            if cells > best_cells {
+                best = *t;
+                best_cells = cells;
+            }
+        }
+
+        (self.transformed(best), best)
+    }
+
     #[inline]
     pub fn len(&self) -> u8 {
         self.fills.len() as u8
     }
 
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fills.is_empty()
+    }
+
     #[inline]
     pub fn remaining_capacity(&self) -> u8 {
         BOARD_CAPACITY - self.len()
@@ -36,10 +113,16 @@ impl Board {
     }
 
     #[inline]
-    pub fn available_selections(&self) -> AvailableSelectionIter {
+    pub fn available_selections(&self) -> AvailableSelectionIter<'_> {
         AvailableSelectionIter::new(self)
     }
 
+    /// Every way the board could end up once fully revealed, scored with the given payouts
+    #[inline]
+    pub fn endings(&self, payouts: &PayoutTable) -> EndingIter {
+        EndingIter::new(self, payouts)
+    }
+
     pub fn col(&self, idx: u8) -> EndRow {
         EndRow::new(
             self.compute_board_pos(idx, 0),
@@ -100,7 +183,20 @@ impl Board {
         })
     }
 
+    /// Check the position & value are on the board, so broken input built without the `try_`
+    /// constructors doesn't get in
+    fn validate(pos: ValuedBoardPosition) -> Result<(), FillFailure> {
+        if !pos.position().is_valid() {
+            return Err(FillFailure::InvalidPosition);
+        } else if !(1..=MAX_VALUE).contains(&pos.value()) {
+            return Err(FillFailure::InvalidValue);
+        }
+
+        Ok(())
+    }
+
     pub fn fill(&mut self, pos: ValuedBoardPosition) -> Result<(), FillFailure> {
+        Self::validate(pos)?;
         if self.contains_value(pos.value()) {
             return Err(FillFailure::ValueAlreadyContained);
         } else if self.contains_position(pos.position()) {
@@ -111,6 +207,37 @@ impl Board {
 
         Ok(())
     }
+
+    /// Clear a revealed cell, returning what was in it
+    pub fn remove(&mut self, pos: BoardPosition) -> Option<ValuedBoardPosition> {
+        let index = self.fills.iter().position(|p| p.position() == pos)?;
+
+        Some(self.fills.remove(index))
+    }
+
+    /// Correct the value of a revealed cell, returning the previous one
+    pub fn replace(
+        &mut self,
+        pos: ValuedBoardPosition,
+    ) -> Result<ValuedBoardPosition, FillFailure> {
+        Self::validate(pos)?;
+        let index = self
+            .fills
+            .iter()
+            .position(|p| p.position() == pos.position())
+            .ok_or(FillFailure::PositionNotFilled)?;
+
+        let old = self.fills[index];
+        if old.value() == pos.value() {
+            return Ok(old);
+        } else if self.contains_value(pos.value()) {
+            return Err(FillFailure::ValueAlreadyContained);
+        }
+
+        self.fills[index] = pos;
+
+        Ok(old)
+    }
 }
 
 impl Default for Board {
@@ -120,3 +247,128 @@ impl Default for Board {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::line::NUM_LINES;
+    use crate::{ExpectedValue, Line, Recommendation};
+
+    #[test]
+    fn eq_ignores_fill_order() {
+        let a = "1__/_5_/___".parse::<Board>().unwrap();
+        let mut b = Board::default();
+        b.fill(ValuedBoardPosition::from_u8(5, 1, 1)).unwrap();
+        b.fill(ValuedBoardPosition::from_u8(1, 0, 0)).unwrap();
+        let c = "5__/_1_/___".parse::<Board>().unwrap();
+
+        assert_ne!(a.fills(), b.fills());
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let set: HashSet<Board> = [a, b, c].iter().cloned().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn canonical() {
+        let board = "1__/__5/_7_".parse::<Board>().unwrap();
+        let (canonical, transform) = board.canonical();
+
+        assert_eq!(board.transformed(transform), canonical);
+        assert_eq!(canonical.transformed(transform.inverse()), board);
+        for t in Transform::ALL.iter() {
+            assert_eq!(board.transformed(*t).canonical().0, canonical, "{}", t);
+        }
+    }
+
+    #[test]
+    fn symmetric_board() {
+        let board = "___/_5_/___".parse::<Board>().unwrap();
+
+        assert_eq!(board.canonical(), (board.clone(), Transform::Identity));
+    }
+
+    #[test]
+    fn recommendation_maps_back() {
+        let board = "_8_/7__/__3".parse::<Board>().unwrap();
+        let (canonical, transform) = board.canonical();
+        let rec = Recommendation::from_board(&board).unwrap();
+        let canon_rec = Recommendation::from_board(&canonical).unwrap();
+
+        let mut line_values = [ExpectedValue::default(); NUM_LINES];
+        for (line, v) in Line::ALL.iter().zip(line_values.iter_mut()) {
+            *v = canon_rec.line_value(*line);
+        }
+        let mapped = transform.lines_from_transformed(&line_values);
+        for line in Line::ALL.iter() {
+            assert_eq!(
+                mapped[line.index() as usize],
+                rec.line_value(*line),
+                "{}",
+                line
+            );
+        }
+
+        let mut reveal_values = [None; BOARD_CAPACITY as usize];
+        for (idx, v) in reveal_values.iter_mut().enumerate() {
+            *v = canon_rec.reveal_value(BoardPosition::from_index(idx as u8));
+        }
+        let mapped = transform.cells_from_transformed(&reveal_values);
+        for (idx, v) in mapped.iter().enumerate() {
+            assert_eq!(*v, rec.reveal_value(BoardPosition::from_index(idx as u8)));
+        }
+
+        for pos in canon_rec.suggestions() {
+            assert!(rec.suggestions().contains(&transform.inverse().apply(*pos)));
+        }
+    }
+
+    #[test]
+    fn remove_and_replace() {
+        let mut board = "1__/_5_/__9".parse::<Board>().unwrap();
+
+        assert_eq!(
+            board.remove(BoardPosition::new(1, 1)),
+            Some(ValuedBoardPosition::from_u8(5, 1, 1))
+        );
+        assert_eq!(board.remove(BoardPosition::new(1, 1)), None);
+        assert_eq!(board, "1__/___/__9".parse::<Board>().unwrap());
+
+        assert_eq!(
+            board.replace(ValuedBoardPosition::from_u8(4, 0, 0)),
+            Ok(ValuedBoardPosition::from_u8(1, 0, 0))
+        );
+        assert_eq!(
+            board.replace(ValuedBoardPosition::from_u8(9, 0, 0)),
+            Err(FillFailure::ValueAlreadyContained)
+        );
+        assert_eq!(
+            board.replace(ValuedBoardPosition::from_u8(2, 1, 0)),
+            Err(FillFailure::PositionNotFilled)
+        );
+        assert_eq!(board, "4__/___/__9".parse::<Board>().unwrap());
+    }
+
+    #[test]
+    fn invalid_fills() {
+        let mut board = "1__/___/___".parse::<Board>().unwrap();
+
+        assert_eq!(
+            board.fill(ValuedBoardPosition::empty(BoardPosition::new(1, 1))),
+            Err(FillFailure::InvalidValue)
+        );
+        assert_eq!(
+            board.fill(ValuedBoardPosition::from_pos(5, BoardPosition::never())),
+            Err(FillFailure::InvalidPosition)
+        );
+        assert_eq!(
+            board.replace(ValuedBoardPosition::empty(BoardPosition::new(0, 0))),
+            Err(FillFailure::InvalidValue)
+        );
+        assert_eq!(board, "1__/___/___".parse::<Board>().unwrap());
+        assert_eq!(board.len(), 1);
+    }
+}
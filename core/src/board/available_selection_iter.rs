@@ -42,7 +42,7 @@ impl<'b> AvailableSelectionIter<'b> {
         self.num_emitted == self.will_emit
     }
 
-    pub fn new(board: &'b Board) -> AvailableSelectionIter<'_> {
+    pub fn new(board: &'b Board) -> AvailableSelectionIter<'b> {
         AvailableSelectionIter {
             board,
             counter: 1,
@@ -0,0 +1,34 @@
+use crate::ValuedBoardPosition;
+
+/// A change made to a [`Board`](super::Board), as kept in a [game](crate::Game)'s undo/redo
+/// history
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Edit {
+    /// A cell got revealed
+    Fill(ValuedBoardPosition),
+    /// A revealed cell got cleared
+    Remove(ValuedBoardPosition),
+    /// A revealed cell's value got corrected
+    Replace {
+        old: ValuedBoardPosition,
+        new: ValuedBoardPosition,
+    },
+}
+
+impl Edit {
+    /// The edit undoing this one
+    pub fn inverse(self) -> Edit {
+        match self {
+            Self::Fill(v) => Self::Remove(v),
+            Self::Remove(v) => Self::Fill(v),
+            Self::Replace { old, new } => Self::Replace { old: new, new: old },
+        }
+    }
+}
+
+/// An edit along with where in the fill order it happened
+#[derive(Debug, Copy, Clone)]
+pub(super) struct HistoryEntry {
+    pub edit: Edit,
+    pub index: usize,
+}
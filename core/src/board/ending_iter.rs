@@ -0,0 +1,194 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::{Board, BOARD_CAPACITY};
+use crate::{BoardPosition, Ending, PayoutTable, ValuedBoardPosition};
+
+const NUM_CELLS: usize = BOARD_CAPACITY as usize;
+
+/// Iterate through every way the board could end up, filling the unrevealed cells in
+/// [position index](BoardPosition::index) order with the lowest values first.
+///
+/// Being an ordinary iterator, it can be filtered on the outcome of a line, e.g. the endings where
+/// the top row pays out at least 3600:
+///
+/// ```
+/// # use cactpot_solver_core::{Board, Line, PayoutTable, ValuedBoardPosition};
+/// let mut board = Board::default();
+/// board.fill(ValuedBoardPosition::from_u8(1, 0, 0)).unwrap();
+///
+/// let num = board
+///     .endings(&PayoutTable::STANDARD)
+///     .filter(|ending| ending.line_payout(Line::Row0) >= 3600)
+///     .count();
+/// // Only the jackpot gets there: 2 & 3 in either order next to the 1
+/// assert_eq!(num, 2 * 720);
+/// ```
+pub struct EndingIter {
+    cells: [u8; NUM_CELLS],
+    empty: [u8; NUM_CELLS],
+    /// Values left over for the empty cells, in the order they're being assigned
+    values: [u8; NUM_CELLS],
+    num_empty: usize,
+    remaining: usize,
+    payouts: PayoutTable,
+}
+
+impl EndingIter {
+    pub fn new(board: &Board, payouts: &PayoutTable) -> EndingIter {
+        let cells = board.cells();
+        let mut empty = [0; NUM_CELLS];
+        let mut num_empty = 0;
+        for (idx, value) in cells.iter().enumerate() {
+            if *value == 0 {
+                empty[num_empty] = idx as u8;
+                num_empty += 1;
+            }
+        }
+
+        let mut values = [0; NUM_CELLS];
+        for (slot, value) in values.iter_mut().zip(board.available_selections()) {
+            *slot = value;
+        }
+
+        EndingIter {
+            cells,
+            empty,
+            values,
+            num_empty,
+            remaining: (1..=num_empty).product(),
+            payouts: *payouts,
+        }
+    }
+
+    /// Same as [`Iterator::count`] but without building every ending
+    #[inline]
+    pub fn num_endings(&self) -> usize {
+        self.remaining
+    }
+
+    /// The `n` best endings by their [ordering](Ending#impl-Ord), best first
+    pub fn best(self, n: usize) -> Vec<Ending> {
+        // Min-heap of the best endings so far, so the worst of them is the one to drop
+        let mut heap = BinaryHeap::with_capacity(n.min(self.remaining) + 1);
+        for ending in self {
+            heap.push(Reverse(ending));
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(ending)| ending)
+            .collect()
+    }
+
+    /// Rearrange the values into the next permutation in lexicographical order
+    fn advance(&mut self) {
+        let values = &mut self.values[..self.num_empty];
+        let pivot = match (1..values.len()).rev().find(|i| values[i - 1] < values[*i]) {
+            Some(v) => v - 1,
+            None => return,
+        };
+        let successor = (pivot + 1..values.len())
+            .rev()
+            .find(|i| values[*i] > values[pivot])
+            .unwrap();
+
+        values.swap(pivot, successor);
+        values[pivot + 1..].reverse();
+    }
+}
+
+impl Iterator for EndingIter {
+    type Item = Ending;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let mut cells = self.cells;
+        for (idx, value) in self.empty[..self.num_empty].iter().zip(self.values.iter()) {
+            cells[*idx as usize] = *value;
+        }
+
+        let mut board = Board::default();
+        for (idx, value) in cells.iter().enumerate() {
+            let pos = BoardPosition::from_index(idx as u8);
+            board.fills.push(ValuedBoardPosition::from_pos(*value, pos));
+        }
+
+        self.remaining -= 1;
+        self.advance();
+
+        Some(Ending::new(board, &self.payouts))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for EndingIter {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::packed_board::PackedBoard;
+    use crate::Line;
+
+    fn values_of(ending: &Ending) -> [u8; NUM_CELLS] {
+        let mut out = [0; NUM_CELLS];
+        for (idx, v) in out.iter_mut().enumerate() {
+            *v = ending.value_at(BoardPosition::from_index(idx as u8));
+        }
+
+        out
+    }
+
+    #[test]
+    fn matches_walker() {
+        let board = "_8_/7__/__3".parse::<Board>().unwrap();
+        let mut exp = Vec::new();
+        PackedBoard::from_board(&board).for_each_ending(|cells| exp.push(*cells));
+
+        let iter = board.endings(&PayoutTable::STANDARD);
+        assert_eq!(iter.len(), 720);
+
+        let actual: Vec<[u8; NUM_CELLS]> = iter.map(|e| values_of(&e)).collect();
+        assert_eq!(actual, exp);
+    }
+
+    #[test]
+    fn full_board() {
+        let board = "123/456/789".parse::<Board>().unwrap();
+        let endings: Vec<Ending> = board.endings(&PayoutTable::STANDARD).collect();
+
+        assert_eq!(endings.len(), 1);
+        assert_eq!(endings[0].board(), &board);
+    }
+
+    #[test]
+    fn best() {
+        let board = "12_/_5_/__9".parse::<Board>().unwrap();
+        let best = board.endings(&PayoutTable::STANDARD).best(5);
+
+        let mut exp: Vec<Ending> = board.endings(&PayoutTable::STANDARD).collect();
+        exp.sort();
+        let worst_kept = &exp[exp.len() - 5];
+
+        assert_eq!(best.len(), 5);
+        assert!(best.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(&best[0], exp.last().unwrap());
+        assert_eq!(&best[4], worst_kept);
+        assert_eq!(best, exp.iter().rev().take(5).cloned().collect::<Vec<_>>());
+        // 1 & 2 in the top row need a 3 for the jackpot
+        assert_eq!(best[0].line_payout(Line::Row0), 10000);
+
+        assert!(board.endings(&PayoutTable::STANDARD).best(0).is_empty());
+        assert_eq!(board.endings(&PayoutTable::STANDARD).best(1000).len(), 120);
+    }
+}
@@ -4,6 +4,11 @@ use std::fmt::{Display, Formatter};
 pub enum FillFailure {
     ValueAlreadyContained,
     PositionAlreadyFilled,
+    PositionNotFilled,
+    /// The value isn't 1 to 9
+    InvalidValue,
+    /// The position isn't on the board
+    InvalidPosition,
 }
 
 impl Display for FillFailure {
@@ -17,6 +22,9 @@ impl AsRef<str> for FillFailure {
         match self {
             Self::PositionAlreadyFilled => "PositionAlreadyFilled",
             Self::ValueAlreadyContained => "ValueAlreadyContained",
+            Self::PositionNotFilled => "PositionNotFilled",
+            Self::InvalidValue => "InvalidValue",
+            Self::InvalidPosition => "InvalidPosition",
         }
     }
 }
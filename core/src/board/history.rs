@@ -0,0 +1,174 @@
+use super::edit::{Edit, HistoryEntry};
+use super::{Board, FillFailure};
+use crate::{BoardPosition, ValuedBoardPosition};
+
+/// A board along with the undo/redo history of the edits made to it
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BoardHistory {
+    board: Board,
+    undo_stack: Vec<HistoryEntry>,
+    redo_stack: Vec<HistoryEntry>,
+}
+
+impl BoardHistory {
+    /// Pick up from the given board with a blank history
+    #[inline]
+    pub fn new(board: Board) -> Self {
+        Self {
+            board,
+            ..Self::default()
+        }
+    }
+
+    #[inline]
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Clear the board along with its history
+    #[inline]
+    pub fn clear(&mut self) {
+        self.board.clear_fills();
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    pub fn fill(&mut self, pos: ValuedBoardPosition) -> Result<(), FillFailure> {
+        self.board.fill(pos)?;
+        self.record(Edit::Fill(pos), self.board.fills.len() - 1);
+
+        Ok(())
+    }
+
+    /// Clear a revealed cell, returning what was in it
+    pub fn remove(&mut self, pos: BoardPosition) -> Option<ValuedBoardPosition> {
+        let index = self.index_of(pos)?;
+        let out = self.board.fills.remove(index);
+        self.record(Edit::Remove(out), index);
+
+        Some(out)
+    }
+
+    /// Correct the value of a revealed cell, returning the previous one
+    pub fn replace(
+        &mut self,
+        pos: ValuedBoardPosition,
+    ) -> Result<ValuedBoardPosition, FillFailure> {
+        let old = self.board.replace(pos)?;
+        if old != pos {
+            let index = self.index_of(pos.position()).unwrap();
+            self.record(Edit::Replace { old, new: pos }, index);
+        }
+
+        Ok(old)
+    }
+
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Take back the most recent edit, returning it
+    pub fn undo(&mut self) -> Option<Edit> {
+        let entry = self.undo_stack.pop()?;
+        self.apply(entry.edit.inverse(), entry.index);
+        self.redo_stack.push(entry);
+
+        Some(entry.edit)
+    }
+
+    /// Make the most recently undone edit again, returning it
+    pub fn redo(&mut self) -> Option<Edit> {
+        let entry = self.redo_stack.pop()?;
+        self.apply(entry.edit, entry.index);
+        self.undo_stack.push(entry);
+
+        Some(entry.edit)
+    }
+
+    /// Where in the fill order the cell got revealed
+    #[inline]
+    fn index_of(&self, pos: BoardPosition) -> Option<usize> {
+        self.board.fills.iter().position(|p| p.position() == pos)
+    }
+
+    fn apply(&mut self, edit: Edit, index: usize) {
+        let fills = &mut self.board.fills;
+        match edit {
+            Edit::Fill(pos) => fills.insert(index, pos),
+            Edit::Remove(_) => {
+                fills.remove(index);
+            }
+            Edit::Replace { new, .. } => fills[index] = new,
+        }
+    }
+
+    /// Add a fresh edit to the history, forgetting anything that could've been redone
+    #[inline]
+    fn record(&mut self, edit: Edit, index: usize) {
+        self.undo_stack.push(HistoryEntry { edit, index });
+        self.redo_stack.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn undo_redo() {
+        let first = ValuedBoardPosition::from_u8(1, 0, 0);
+        let second = ValuedBoardPosition::from_u8(5, 1, 1);
+        let third = ValuedBoardPosition::from_u8(9, 2, 2);
+        let mut history = BoardHistory::default();
+        for pos in [first, second, third].iter() {
+            history.fill(*pos).unwrap();
+        }
+        history.remove(second.position());
+        history
+            .replace(ValuedBoardPosition::from_u8(2, 0, 0))
+            .unwrap();
+
+        assert!(history.undo().is_some());
+        assert_eq!(history.undo(), Some(Edit::Remove(second)));
+        // Back where it was in the fill order
+        assert_eq!(history.board().fills(), &[first, second, third]);
+        assert_eq!(history.undo(), Some(Edit::Fill(third)));
+
+        assert_eq!(history.redo(), Some(Edit::Fill(third)));
+        assert_eq!(history.redo(), Some(Edit::Remove(second)));
+        assert!(history.can_redo());
+
+        // A fresh edit drops what could've been redone
+        history.fill(second).unwrap();
+        assert!(!history.can_redo());
+        assert_eq!(history.redo(), None);
+        assert_eq!(history.board().fills(), &[first, third, second]);
+
+        while history.undo().is_some() {}
+        assert!(history.board().is_empty());
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn failed_edits_stay_out() {
+        let mut history = BoardHistory::new("1__/_5_/__9".parse().unwrap());
+        assert!(!history.can_undo());
+
+        assert!(history.fill(ValuedBoardPosition::from_u8(5, 0, 1)).is_err());
+        assert_eq!(history.remove(BoardPosition::new(1, 0)), None);
+        // Same value as before
+        history
+            .replace(ValuedBoardPosition::from_u8(5, 1, 1))
+            .unwrap();
+        assert!(!history.can_undo());
+
+        history.clear();
+        assert!(history.board().is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+//! Compact text notation for boards: one character per cell, row by row, with the rows optionally
+//! separated by `/`. Revealed cells are written as their value & hidden ones as `_`.
+//!
+//! ```
+//! use cactpot_solver_core::{Board, BoardPosition};
+//!
+//! let board: Board = "1__/_5_/__9".parse().unwrap();
+//! assert_eq!(board.find(BoardPosition::new(1, 1)).unwrap().value(), 5);
+//! assert_eq!(board, "1___5___9".parse().unwrap());
+//! assert_eq!(board.to_string(), "1__/_5_/__9");
+//! ```
+//!
+//! With the `serde` feature, boards are serialized as a string in this notation, e.g.
+//! `"1__/_5_/__9"`. Only the revealed cells are kept: the fill order doesn't make it through.
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use super::{Board, FillFailure, BOARD_CAPACITY};
+use crate::{BoardPosition, ValuedBoardPosition};
+
+const HIDDEN: char = '_';
+const ROW_SEPARATOR: char = '/';
+
+/// Why a string isn't a valid board
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseBoardError {
+    /// Boards without row separators must have exactly 9 cells; contains the number of cells given
+    InvalidLength(usize),
+    /// Boards with row separators must have exactly 3 rows; contains the number of rows given
+    InvalidRowCount(usize),
+    /// Every row must have exactly 3 cells
+    InvalidRowLength { row: u8, len: usize },
+    /// A cell must be a value from 1 to 9 or `_`
+    InvalidCell {
+        position: BoardPosition,
+        found: char,
+    },
+    /// The same value appears in more than one cell; contains the second cell
+    DuplicateValue(ValuedBoardPosition),
+}
+
+impl Display for ParseBoardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::InvalidLength(len) => {
+                write!(f, "Expected {} cells, but got {}", BOARD_CAPACITY, len)
+            }
+            Self::InvalidRowCount(n) => write!(f, "Expected 3 rows, but got {}", n),
+            Self::InvalidRowLength { row, len } => {
+                write!(f, "Expected 3 cells in row {}, but got {}", row, len)
+            }
+            Self::InvalidCell { position, found } => write!(
+                f,
+                "Invalid cell {:?} at {}; expected a value from 1 to 9 or {:?}",
+                found, position, HIDDEN
+            ),
+            Self::DuplicateValue(pos) => write!(
+                f,
+                "Value {} at {} already appears elsewhere on the board",
+                pos.value(),
+                pos.position()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseBoardError {}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for (idx, value) in self.cells().iter().enumerate() {
+            if idx != 0 && idx % 3 == 0 {
+                write!(f, "{}", ROW_SEPARATOR)?;
+            }
+            match value {
+                0 => write!(f, "{}", HIDDEN)?,
+                v => write!(f, "{}", v)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Board {
+    type Err = ParseBoardError;
+
+    /// Parse a board in the [notation](crate::board::notation) `1__/_5_/__9` or `1___5___9`.
+    /// Surrounding whitespace is ignored; cells get filled in position order.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let cells = match s.contains(ROW_SEPARATOR) {
+            true => split_rows(s)?,
+            false => {
+                let len = s.chars().count();
+                if len != BOARD_CAPACITY as usize {
+                    return Err(ParseBoardError::InvalidLength(len));
+                }
+                s.chars().collect()
+            }
+        };
+
+        let mut board = Board::default();
+        for (idx, c) in cells.into_iter().enumerate() {
+            let position = BoardPosition::from_index(idx as u8);
+            let value = match c {
+                HIDDEN => continue,
+                '1'..='9' => c as u8 - b'0',
+                found => return Err(ParseBoardError::InvalidCell { position, found }),
+            };
+
+            let pos = ValuedBoardPosition::from_pos(value, position);
+            match board.fill(pos) {
+                Ok(()) => {}
+                Err(FillFailure::ValueAlreadyContained) => {
+                    return Err(ParseBoardError::DuplicateValue(pos))
+                }
+                Err(e) => unreachable!("Cells are checked & only visited once; got {}", e),
+            }
+        }
+
+        Ok(board)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Board {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Board {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The cells of a board with row separators, in position order
+fn split_rows(s: &str) -> Result<Vec<char>, ParseBoardError> {
+    let rows: Vec<&str> = s.split(ROW_SEPARATOR).collect();
+    if rows.len() != 3 {
+        return Err(ParseBoardError::InvalidRowCount(rows.len()));
+    }
+
+    let mut out = Vec::with_capacity(BOARD_CAPACITY as usize);
+    for (row, cells) in rows.iter().enumerate() {
+        let len = cells.chars().count();
+        if len != 3 {
+            return Err(ParseBoardError::InvalidRowLength {
+                row: row as u8,
+                len,
+            });
+        }
+        out.extend(cells.chars());
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Board::default().to_string(), "___/___/___");
+
+        // Written in position order, whatever order the cells got revealed in
+        let mut board = Board::default();
+        for (value, col, row) in [(7, 2, 2), (1, 0, 0), (5, 1, 1)].iter() {
+            board
+                .fill(ValuedBoardPosition::from_u8(*value, *col, *row))
+                .unwrap();
+        }
+        assert_eq!(board.to_string(), "1__/_5_/__7");
+    }
+
+    #[test]
+    fn parse() {
+        let exp = "1__/_5_/__9".parse::<Board>().unwrap();
+
+        assert_eq!("1__/_5_/__9".parse(), Ok(exp.clone()));
+        assert_eq!("1___5___9".parse(), Ok(exp.clone()));
+        assert_eq!("  1__/_5_/__9\n".parse(), Ok(exp));
+        assert_eq!("___/___/___".parse(), Ok(Board::default()));
+
+        let board: Board = "_3_/___/8__".parse().unwrap();
+        assert_eq!(board.to_string().parse(), Ok(board));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let board = "1__/_5_/__9".parse::<Board>().unwrap();
+        let json = serde_json::to_string(&board).unwrap();
+
+        assert_eq!(json, r#""1__/_5_/__9""#);
+        assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+        assert!(serde_json::from_str::<Board>(r#""1__/_5_/__1""#).is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let parse = |s: &str| s.parse::<Board>().unwrap_err();
+
+        assert_eq!(parse(""), ParseBoardError::InvalidLength(0));
+        assert_eq!(parse("1___5___"), ParseBoardError::InvalidLength(8));
+        assert_eq!(parse("1__/_5_"), ParseBoardError::InvalidRowCount(2));
+        assert_eq!(parse("1__/_5_/__9/"), ParseBoardError::InvalidRowCount(4));
+        assert_eq!(
+            parse("1__/_5__/_9"),
+            ParseBoardError::InvalidRowLength { row: 1, len: 4 }
+        );
+        assert_eq!(
+            parse("1__/_0_/__9"),
+            ParseBoardError::InvalidCell {
+                position: BoardPosition::new(1, 1),
+                found: '0'
+            }
+        );
+        assert_eq!(
+            parse("1___x___9"),
+            ParseBoardError::InvalidCell {
+                position: BoardPosition::new(1, 1),
+                found: 'x'
+            }
+        );
+        assert_eq!(
+            parse("1__/_5_/__1"),
+            ParseBoardError::DuplicateValue(ValuedBoardPosition::from_u8(1, 2, 2))
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::line::NUM_LINES;
+use crate::{BoardPosition, Line};
+
+const NUM_CELLS: usize = 9;
+
+/// One of the 8 symmetries of the 3x3 grid: rotations are clockwise, flips mirror across the
+/// named axis
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Transform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Swaps the left & right columns
+    FlipHorizontal,
+    /// Swaps the top & bottom rows
+    FlipVertical,
+    /// Mirrors across the top left to bottom right diagonal
+    Transpose,
+    /// Mirrors across the bottom left to top right diagonal
+    AntiTranspose,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::Transpose,
+        Transform::AntiTranspose,
+    ];
+
+    /// The transform undoing this one
+    pub fn inverse(&self) -> Self {
+        match self {
+            Self::Rotate90 => Self::Rotate270,
+            Self::Rotate270 => Self::Rotate90,
+            other => *other,
+        }
+    }
+
+    /// Where the given cell ends up
+    pub fn apply(&self, pos: BoardPosition) -> BoardPosition {
+        let (col, row) = (pos.col(), pos.row());
+        let (col, row) = match self {
+            Self::Identity => (col, row),
+            Self::Rotate90 => (2 - row, col),
+            Self::Rotate180 => (2 - col, 2 - row),
+            Self::Rotate270 => (row, 2 - col),
+            Self::FlipHorizontal => (2 - col, row),
+            Self::FlipVertical => (col, 2 - row),
+            Self::Transpose => (row, col),
+            Self::AntiTranspose => (2 - row, 2 - col),
+        };
+
+        BoardPosition::new(col, row)
+    }
+
+    /// Where the given line ends up
+    pub fn apply_line(&self, line: Line) -> Line {
+        let mut cells = line.positions();
+        for pos in cells.iter_mut() {
+            *pos = self.apply(*pos);
+        }
+
+        *Line::ALL
+            .iter()
+            .find(|l| l.positions().iter().all(|p| cells.contains(p)))
+            .unwrap()
+    }
+
+    /// Given per-cell values of the transformed board, ordered by
+    /// [position index](BoardPosition::index), get them for the original board
+    pub fn cells_from_transformed<T: Copy>(&self, values: &[T; NUM_CELLS]) -> [T; NUM_CELLS] {
+        let mut out = *values;
+        for (idx, v) in out.iter_mut().enumerate() {
+            *v = values[self.apply(BoardPosition::from_index(idx as u8)).index() as usize];
+        }
+
+        out
+    }
+
+    /// Given per-line values of the transformed board, e.g. line averages, ordered by
+    /// [line index](Line::index), get them for the original board
+    pub fn lines_from_transformed<T: Copy>(&self, values: &[T; NUM_LINES]) -> [T; NUM_LINES] {
+        let mut out = *values;
+        for (line, v) in Line::ALL.iter().zip(out.iter_mut()) {
+            *v = values[self.apply_line(*line).index() as usize];
+        }
+
+        out
+    }
+}
+
+impl Default for Transform {
+    #[inline]
+    fn default() -> Self {
+        Self::Identity
+    }
+}
+
+impl Display for Transform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Identity => f.write_str("Identity"),
+            Self::Rotate90 => f.write_str("Rotate 90°"),
+            Self::Rotate180 => f.write_str("Rotate 180°"),
+            Self::Rotate270 => f.write_str("Rotate 270°"),
+            Self::FlipHorizontal => f.write_str("Flip horizontally"),
+            Self::FlipVertical => f.write_str("Flip vertically"),
+            Self::Transpose => f.write_str("Transpose"),
+            Self::AntiTranspose => f.write_str("Anti-transpose"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inverse() {
+        for t in Transform::ALL.iter() {
+            for idx in 0..9 {
+                let pos = BoardPosition::from_index(idx);
+                assert_eq!(t.inverse().apply(t.apply(pos)), pos, "{} {}", t, pos);
+            }
+        }
+    }
+
+    #[test]
+    fn distinct() {
+        let top_left_image: Vec<(BoardPosition, BoardPosition)> = Transform::ALL
+            .iter()
+            .map(|t| {
+                (
+                    t.apply(BoardPosition::new(0, 0)),
+                    t.apply(BoardPosition::new(1, 0)),
+                )
+            })
+            .collect();
+
+        for (i, a) in top_left_image.iter().enumerate() {
+            for b in top_left_image[i + 1..].iter() {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(Transform::Rotate90.apply_line(Line::Row0), Line::Col2);
+        assert_eq!(Transform::Rotate90.apply_line(Line::Col0), Line::Row0);
+        assert_eq!(
+            Transform::Rotate90.apply_line(Line::TopLeftBottomRight),
+            Line::BottomLeftTopRight
+        );
+        assert_eq!(Transform::FlipVertical.apply_line(Line::Row0), Line::Row2);
+        assert_eq!(Transform::Transpose.apply_line(Line::Col1), Line::Row1);
+        assert_eq!(
+            Transform::Transpose.apply_line(Line::TopLeftBottomRight),
+            Line::TopLeftBottomRight
+        );
+    }
+}
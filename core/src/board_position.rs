@@ -1,22 +1,70 @@
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::hash::{Hash, Hasher};
 
-use singletons::*;
-
-use super::end_row::DiagRow;
+use super::Line;
+use position_error::PositionError;
 
+pub mod position_error;
 pub mod valued_board_position;
 
 pub const MAX_IDX: u8 = 8;
 pub const MAX_POS: u8 = 2;
 
-/// A position on the board
+/// A position on the board.
+///
+/// Serialized as `{"col": 0, "row": 2}`.
 #[derive(Eq, Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(into = "PositionRepr", try_from = "PositionRepr")
+)]
 pub struct BoardPosition {
     col: u8,
     row: u8,
     index: u8,
 }
 
+/// Serialized form of a [`BoardPosition`]; the index is derived from the col & row
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PositionRepr {
+    col: u8,
+    row: u8,
+}
+
+#[cfg(feature = "serde")]
+impl From<BoardPosition> for PositionRepr {
+    #[inline]
+    fn from(pos: BoardPosition) -> Self {
+        Self {
+            col: pos.col,
+            row: pos.row,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PositionRepr> for BoardPosition {
+    type Error = PositionError;
+
+    #[inline]
+    fn try_from(repr: PositionRepr) -> Result<Self, Self::Error> {
+        Self::try_new(repr.col, repr.row)
+    }
+}
+
+/// Same as [`BoardPosition::try_new`] from a `(col, row)` pair
+impl TryFrom<(u8, u8)> for BoardPosition {
+    type Error = PositionError;
+
+    #[inline]
+    fn try_from((col, row): (u8, u8)) -> Result<Self, Self::Error> {
+        Self::try_new(col, row)
+    }
+}
+
 impl PartialEq for BoardPosition {
     #[inline]
     fn eq(&self, other: &BoardPosition) -> bool {
@@ -24,6 +72,13 @@ impl PartialEq for BoardPosition {
     }
 }
 
+impl Hash for BoardPosition {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
 impl Display for BoardPosition {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         Display::fmt(&self.col, f)?;
@@ -42,6 +97,8 @@ impl BoardPosition {
         }
     }
 
+    /// Position at the given col & row. Out of range input is only caught in debug builds; use
+    /// [`try_new`](Self::try_new) for anything untrusted.
     #[inline]
     pub fn new(col: u8, row: u8) -> Self {
         debug_assert!(
@@ -58,16 +115,36 @@ impl BoardPosition {
         }
     }
 
+    /// Same as [`new`](Self::new), but checks the col & row at runtime
+    pub fn try_new(col: u8, row: u8) -> Result<Self, PositionError> {
+        if col > MAX_POS {
+            return Err(PositionError::InvalidCol(col));
+        } else if row > MAX_POS {
+            return Err(PositionError::InvalidRow(row));
+        }
+
+        Ok(Self::new(col, row))
+    }
+
+    /// Whether the col & row are on the board
+    #[inline]
+    pub(crate) fn is_valid(&self) -> bool {
+        self.col <= MAX_POS && self.row <= MAX_POS
+    }
+
     #[inline]
     fn pos_index(col: u8, row: u8) -> u8 {
         col + (row * 3)
     }
 
+    /// Lines going through the cell, ordered by [line index](Line::index)
     #[inline]
-    pub fn diag_row(&self) -> DiagRow {
-        LOOKUP_DIAG_ROW[self.col as usize][self.row as usize]
+    pub fn lines(self) -> impl Iterator<Item = Line> {
+        Line::iter().filter(move |line| line.contains(self))
     }
 
+    /// Position at the given [index](Self::index). Out of range input is only caught in debug
+    /// builds; use [`try_from_index`](Self::try_from_index) for anything untrusted.
     pub fn from_index(index: u8) -> Self {
         debug_assert!(index <= MAX_IDX, "Index out of bounds: {}", index);
 
@@ -80,6 +157,14 @@ impl BoardPosition {
         }
     }
 
+    /// Same as [`from_index`](Self::from_index), but checks the index at runtime
+    pub fn try_from_index(index: u8) -> Result<Self, PositionError> {
+        match index <= MAX_IDX {
+            true => Ok(Self::from_index(index)),
+            false => Err(PositionError::InvalidIndex(index)),
+        }
+    }
+
     #[inline]
     pub fn eq(&self, col: u8, row: u8) -> bool {
         col == self.col && row == self.row
@@ -135,22 +220,55 @@ mod test {
     }
 
     #[test]
-    fn diag_row() {
-        let specs = [
-            DiagRow::TopLeftBottomRight,
-            DiagRow::None,
-            DiagRow::BottomLeftTopRight,
-            DiagRow::None,
-            DiagRow::Both,
-            DiagRow::None,
-            DiagRow::BottomLeftTopRight,
-            DiagRow::None,
-            DiagRow::TopLeftBottomRight,
-        ];
-        for i in 0..specs.len() {
-            let pos = BoardPosition::from_index(i as u8);
-            assert_eq!(pos.diag_row(), specs[i], "{}, {}", pos.col, pos.row);
-        }
+    fn lines() {
+        let lines = |col, row| BoardPosition::new(col, row).lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            lines(1, 1),
+            vec![
+                Line::Col1,
+                Line::Row1,
+                Line::TopLeftBottomRight,
+                Line::BottomLeftTopRight
+            ]
+        );
+        assert_eq!(
+            lines(0, 2),
+            vec![Line::Col0, Line::Row2, Line::BottomLeftTopRight]
+        );
+        assert_eq!(lines(1, 0), vec![Line::Col1, Line::Row0]);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(BoardPosition::try_new(2, 1), Ok(BoardPosition::new(2, 1)));
+        assert_eq!(
+            BoardPosition::try_new(3, 1),
+            Err(PositionError::InvalidCol(3))
+        );
+        assert_eq!(
+            BoardPosition::try_from((0, 200)),
+            Err(PositionError::InvalidRow(200))
+        );
+        assert_eq!(
+            BoardPosition::try_from_index(8),
+            Ok(BoardPosition::new(2, 2))
+        );
+        assert_eq!(
+            BoardPosition::try_from_index(9),
+            Err(PositionError::InvalidIndex(9))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let pos = BoardPosition::new(2, 1);
+        let json = serde_json::to_string(&pos).unwrap();
+
+        assert_eq!(json, r#"{"col":2,"row":1}"#);
+        assert_eq!(serde_json::from_str::<BoardPosition>(&json).unwrap(), pos);
+        assert!(serde_json::from_str::<BoardPosition>(r#"{"col":3,"row":1}"#).is_err());
     }
 
     #[test]
@@ -182,21 +300,9 @@ mod test {
             BoardPosition::new(2, 2),
         ];
 
-        for i in 0..exps.len() {
+        for (i, e) in exps.iter().enumerate() {
             let fi = BoardPosition::from_index(i as u8);
-            let e = exps[i];
-            assert_eq!(fi, e, "{},{} (idx {})", e.col, e.row, e.index);
+            assert_eq!(fi, *e, "{},{} (idx {})", e.col, e.row, e.index);
         }
     }
 }
-
-pub mod singletons {
-
-    use super::DiagRow::{self, *};
-
-    pub const LOOKUP_DIAG_ROW: [[DiagRow; 3]; 3] = [
-        [TopLeftBottomRight, None, BottomLeftTopRight],
-        [None, Both, None],
-        [BottomLeftTopRight, None, TopLeftBottomRight],
-    ];
-}
@@ -0,0 +1,30 @@
+use std::fmt::{Display, Formatter};
+
+use super::valued_board_position::MAX_VALUE;
+use super::{MAX_IDX, MAX_POS};
+
+/// Input that doesn't make a valid position
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PositionError {
+    /// Cols go from 0 to 2
+    InvalidCol(u8),
+    /// Rows go from 0 to 2
+    InvalidRow(u8),
+    /// Position indices go from 0 to 8
+    InvalidIndex(u8),
+    /// Values go from 1 to 9
+    InvalidValue(u8),
+}
+
+impl Display for PositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCol(v) => write!(f, "Invalid col {}; expected 0 to {}", v, MAX_POS),
+            Self::InvalidRow(v) => write!(f, "Invalid row {}; expected 0 to {}", v, MAX_POS),
+            Self::InvalidIndex(v) => write!(f, "Invalid index {}; expected 0 to {}", v, MAX_IDX),
+            Self::InvalidValue(v) => write!(f, "Invalid value {}; expected 1 to {}", v, MAX_VALUE),
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
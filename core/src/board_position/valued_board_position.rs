@@ -1,16 +1,68 @@
+use std::convert::TryFrom;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 
+use super::position_error::PositionError;
 use super::BoardPosition;
 
 pub const MAX_VALUE: u8 = 9;
 
-/// A board position with user selection
+/// A board position with user selection.
+///
+/// Serialized as `{"col": 0, "row": 2, "value": 7}`, a value of 0 meaning nothing's selected.
 #[derive(Eq, PartialEq, Debug, Default, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(into = "ValuedPositionRepr", try_from = "ValuedPositionRepr")
+)]
 pub struct ValuedBoardPosition {
     position: BoardPosition,
     value: u8,
 }
 
+/// Serialized form of a [`ValuedBoardPosition`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ValuedPositionRepr {
+    col: u8,
+    row: u8,
+    value: u8,
+}
+
+#[cfg(feature = "serde")]
+impl From<ValuedBoardPosition> for ValuedPositionRepr {
+    #[inline]
+    fn from(pos: ValuedBoardPosition) -> Self {
+        Self {
+            col: pos.position.col(),
+            row: pos.position.row(),
+            value: pos.value,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ValuedPositionRepr> for ValuedBoardPosition {
+    type Error = PositionError;
+
+    fn try_from(repr: ValuedPositionRepr) -> Result<Self, Self::Error> {
+        match repr.value {
+            0 => BoardPosition::try_new(repr.col, repr.row).map(Self::empty),
+            v => Self::try_from_u8(v, repr.col, repr.row),
+        }
+    }
+}
+
+/// Same as [`ValuedBoardPosition::try_from_u8`] from a `(value, col, row)` triple
+impl TryFrom<(u8, u8, u8)> for ValuedBoardPosition {
+    type Error = PositionError;
+
+    #[inline]
+    fn try_from((value, col, row): (u8, u8, u8)) -> Result<Self, Self::Error> {
+        Self::try_from_u8(value, col, row)
+    }
+}
+
 impl Display for ValuedBoardPosition {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
@@ -19,6 +71,8 @@ impl Display for ValuedBoardPosition {
 }
 
 impl ValuedBoardPosition {
+    /// Out of range values are only caught in debug builds; use
+    /// [`try_from_pos`](Self::try_from_pos) for anything untrusted.
     #[inline]
     pub fn from_pos(value: u8, position: BoardPosition) -> Self {
         debug_assert!(
@@ -36,6 +90,20 @@ impl ValuedBoardPosition {
         Self::from_pos(value, BoardPosition::new(col, row))
     }
 
+    /// Same as [`from_pos`](Self::from_pos), but checks the value is 1 to 9 at runtime
+    pub fn try_from_pos(value: u8, position: BoardPosition) -> Result<Self, PositionError> {
+        match (1..=MAX_VALUE).contains(&value) {
+            true => Ok(Self::from_pos(value, position)),
+            false => Err(PositionError::InvalidValue(value)),
+        }
+    }
+
+    /// Same as [`from_u8`](Self::from_u8), but checks the value, col & row at runtime
+    #[inline]
+    pub fn try_from_u8(value: u8, col: u8, row: u8) -> Result<Self, PositionError> {
+        Self::try_from_pos(value, BoardPosition::try_new(col, row)?)
+    }
+
     /// Nothing selected at the given position
     #[inline]
     pub fn empty(position: BoardPosition) -> Self {
@@ -55,7 +123,9 @@ impl ValuedBoardPosition {
 
 #[cfg(test)]
 mod test {
-    use crate::{BoardPosition, ValuedBoardPosition};
+    use std::convert::TryFrom;
+
+    use crate::{BoardPosition, PositionError, ValuedBoardPosition};
 
     #[test]
     fn from_pos() {
@@ -91,6 +161,47 @@ mod test {
         }
     }
 
+    #[test]
+    fn try_from_u8() {
+        assert_eq!(
+            ValuedBoardPosition::try_from_u8(9, 2, 0),
+            Ok(ValuedBoardPosition::from_u8(9, 2, 0))
+        );
+        assert_eq!(
+            ValuedBoardPosition::try_from_u8(0, 2, 0),
+            Err(PositionError::InvalidValue(0))
+        );
+        assert_eq!(
+            ValuedBoardPosition::try_from((10, 2, 0)),
+            Err(PositionError::InvalidValue(10))
+        );
+        assert_eq!(
+            ValuedBoardPosition::try_from_u8(1, 2, 3),
+            Err(PositionError::InvalidRow(3))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let pos = ValuedBoardPosition::from_u8(7, 0, 2);
+        let json = serde_json::to_string(&pos).unwrap();
+
+        assert_eq!(json, r#"{"col":0,"row":2,"value":7}"#);
+        assert_eq!(
+            serde_json::from_str::<ValuedBoardPosition>(&json).unwrap(),
+            pos
+        );
+        for bad in [
+            r#"{"col":0,"row":3,"value":7}"#,
+            r#"{"col":0,"row":2,"value":10}"#,
+        ]
+        .iter()
+        {
+            assert!(serde_json::from_str::<ValuedBoardPosition>(bad).is_err());
+        }
+    }
+
     #[test]
     fn empty() {
         for pos_idx in 0u8..9 {
@@ -0,0 +1,214 @@
+use std::cmp::Ordering;
+
+use super::line::NUM_LINES;
+use super::parsed_board::ParsedBoard;
+#[cfg(feature = "serde")]
+use super::ValuedBoardPosition;
+use super::{Board, BoardPosition, Line, LineMap, PayoutTable};
+
+/// A way the board could end up once every cell's revealed. Orders by the average payout of its
+/// lines, then by the best one, then by the values of its cells in
+/// [position index](BoardPosition::index) order so only identical endings compare equal.
+///
+/// Serialized as `{"cells": [..], "line_payouts": ..}`: the value of each cell in position index
+/// order, which must hold every value from 1 to 9 once, & what each line pays out in a
+/// [`LineMap`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "EndingRepr", try_from = "EndingRepr"))]
+pub struct Ending(ParsedBoard);
+
+/// Serialized form of an [`Ending`]; see its docs for the fields
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct EndingRepr {
+    cells: [u8; 9],
+    line_payouts: LineMap<u16>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Ending> for EndingRepr {
+    fn from(ending: Ending) -> Self {
+        Self {
+            cells: ending.board().cells(),
+            line_payouts: ending.line_payouts(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<EndingRepr> for Ending {
+    type Error = &'static str;
+
+    fn try_from(repr: EndingRepr) -> Result<Self, Self::Error> {
+        let mut board = Board::default();
+        for (idx, value) in (0u8..).zip(repr.cells.iter()) {
+            let pos = ValuedBoardPosition::from_pos(*value, BoardPosition::from_index(idx));
+            board
+                .fill(pos)
+                .map_err(|_| "An ending needs every value from 1 to 9 once")?;
+        }
+
+        Ok(Self(ParsedBoard::from_line_payouts(
+            board,
+            *repr.line_payouts.values(),
+        )))
+    }
+}
+
+impl Ending {
+    #[inline]
+    pub(crate) fn new(board: Board, payouts: &PayoutTable) -> Self {
+        Self(ParsedBoard::from_board(board, payouts))
+    }
+
+    /// The full board
+    #[inline]
+    pub fn board(&self) -> &Board {
+        self.0.board()
+    }
+
+    #[inline]
+    pub fn value_at(&self, pos: BoardPosition) -> u8 {
+        self.board()
+            .find(pos)
+            .map(|v| v.value())
+            .unwrap_or_default()
+    }
+
+    /// Points the line adds up to
+    #[inline]
+    pub fn line_sum(&self, line: Line) -> u8 {
+        self.0.line_sums()[line.index() as usize]
+    }
+
+    /// What picking the line pays out
+    #[inline]
+    pub fn line_payout(&self, line: Line) -> u16 {
+        self.0.line_payouts()[line.index() as usize]
+    }
+
+    #[inline]
+    pub fn line_payouts(&self) -> LineMap<u16> {
+        LineMap::from_array(self.0.line_payouts())
+    }
+
+    /// Payout of the best line
+    #[inline]
+    pub fn max_payout(&self) -> u16 {
+        self.0.max_payout()
+    }
+
+    /// Payout of the worst line
+    #[inline]
+    pub fn min_payout(&self) -> u16 {
+        self.0.min_payout()
+    }
+
+    /// Mean payout of the lines, rounded down
+    #[inline]
+    pub fn average_payout(&self) -> u16 {
+        self.0.average_payout()
+    }
+
+    /// Lines paying out the [most](Self::max_payout)
+    pub fn best_lines(&self) -> Vec<Line> {
+        let payouts = self.0.line_payouts();
+        let max = self.max_payout();
+
+        (0..NUM_LINES)
+            .filter(|idx| payouts[*idx] == max)
+            .map(|idx| Line::from_index(idx as u8))
+            .collect()
+    }
+}
+
+impl PartialEq for Ending {
+    #[inline]
+    fn eq(&self, other: &Ending) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ending {}
+
+impl PartialOrd for Ending {
+    #[inline]
+    fn partial_cmp(&self, other: &Ending) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ending {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .cmp(&other.0)
+            .then_with(|| self.board().cells().cmp(&other.board().cells()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ValuedBoardPosition;
+
+    fn mkending(values: [u8; 9]) -> Ending {
+        let mut board = Board::default();
+        for (idx, value) in values.iter().enumerate() {
+            let pos = BoardPosition::from_index(idx as u8);
+            board
+                .fill(ValuedBoardPosition::from_pos(*value, pos))
+                .unwrap();
+        }
+
+        Ending::new(board, &PayoutTable::STANDARD)
+    }
+
+    #[test]
+    fn lines() {
+        let ending = mkending([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        assert_eq!(ending.value_at(BoardPosition::new(2, 1)), 6);
+        assert_eq!(ending.line_sum(Line::Row0), 6);
+        assert_eq!(ending.line_sum(Line::Col2), 18);
+        assert_eq!(ending.line_sum(Line::BottomLeftTopRight), 15);
+        assert_eq!(ending.line_payout(Line::Row0), 10000);
+        assert_eq!(ending.line_payouts()[Line::Col2], 119);
+        assert_eq!(ending.max_payout(), 10000);
+        assert_eq!(ending.best_lines(), vec![Line::Row0]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let ending = mkending([1, 5, 9, 6, 7, 2, 8, 3, 4]);
+        let json = serde_json::to_string(&ending).unwrap();
+        let back = serde_json::from_str::<Ending>(&json).unwrap();
+
+        assert_eq!(back, ending);
+        assert_eq!(back.line_payouts(), ending.line_payouts());
+        assert_eq!(back.average_payout(), ending.average_payout());
+
+        let mut value = serde_json::to_value(&ending).unwrap();
+        value["cells"][1] = 1.into();
+        assert!(serde_json::from_value::<Ending>(value).is_err());
+    }
+
+    #[test]
+    fn ord() {
+        let jackpot = mkending([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let other = mkending([1, 5, 9, 6, 7, 2, 8, 3, 4]);
+
+        assert!(jackpot.average_payout() > other.average_payout());
+        assert!(jackpot > other);
+
+        // Mirrored, so the lines & their payouts are the same
+        let mirrored = mkending([3, 2, 1, 6, 5, 4, 9, 8, 7]);
+        assert_eq!(jackpot.average_payout(), mirrored.average_payout());
+        assert_eq!(jackpot.max_payout(), mirrored.max_payout());
+        assert!(jackpot < mirrored);
+        assert_ne!(jackpot, mirrored);
+        assert_eq!(jackpot.cmp(&jackpot.clone()), Ordering::Equal);
+    }
+}
@@ -0,0 +1,201 @@
+use std::cmp::Ordering;
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// An exact expected payout, kept as a fraction so comparisons aren't thrown off by rounding.
+///
+/// Use [`rounded`](Self::rounded) or the [`Display`] impl for showing the value to a human and the
+/// comparison operators for anything else.
+///
+/// Serialized as `{"total": 540, "count": 3}`. A count of 0 only goes with a total of 0.
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(into = "ExpectedValueRepr", try_from = "ExpectedValueRepr")
+)]
+pub struct ExpectedValue {
+    total: u64,
+    count: u64,
+}
+
+/// Serialized form of an [`ExpectedValue`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ExpectedValueRepr {
+    total: u64,
+    count: u64,
+}
+
+#[cfg(feature = "serde")]
+impl From<ExpectedValue> for ExpectedValueRepr {
+    #[inline]
+    fn from(v: ExpectedValue) -> Self {
+        Self {
+            total: v.total,
+            count: v.count,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ExpectedValueRepr> for ExpectedValue {
+    type Error = &'static str;
+
+    #[inline]
+    fn try_from(repr: ExpectedValueRepr) -> Result<Self, Self::Error> {
+        Self::try_new(repr.total, repr.count).ok_or("An expected value with a total needs a count")
+    }
+}
+
+impl ExpectedValue {
+    /// Total payout across a number of outcomes. A total with no outcomes is only caught in debug
+    /// builds; use [`try_new`](Self::try_new) for anything untrusted.
+    #[inline]
+    pub fn new(total: u64, count: u64) -> Self {
+        debug_assert!(count != 0 || total == 0, "Zero count for total {}", total);
+
+        Self { total, count }
+    }
+
+    /// Same as [`new`](Self::new), but `None` for a total with no outcomes
+    #[inline]
+    pub fn try_new(total: u64, count: u64) -> Option<Self> {
+        match count != 0 || total == 0 {
+            true => Some(Self::new(total, count)),
+            false => None,
+        }
+    }
+
+    /// Numerator: sum of the payouts
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Denominator: number of equally likely outcomes
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    #[inline]
+    pub fn as_f64(&self) -> f64 {
+        match self.count {
+            0 => 0.0,
+            count => self.total as f64 / count as f64,
+        }
+    }
+
+    /// Rounded to the nearest whole MGP, halves rounding up. For display only.
+    #[inline]
+    pub fn rounded(&self) -> u64 {
+        match self.count {
+            0 => 0,
+            count => (self.total * 2 + count) / (count * 2),
+        }
+    }
+
+    #[inline]
+    fn cross(&self, other: &Self) -> (u128, u128) {
+        (
+            self.total as u128 * other.count.max(1) as u128,
+            other.total as u128 * self.count.max(1) as u128,
+        )
+    }
+}
+
+impl PartialEq for ExpectedValue {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = self.cross(other);
+        a == b
+    }
+}
+
+impl Eq for ExpectedValue {}
+
+impl PartialOrd for ExpectedValue {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ExpectedValue {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = self.cross(other);
+        a.cmp(&b)
+    }
+}
+
+impl Display for ExpectedValue {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(&self.rounded(), f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn try_new() {
+        assert_eq!(
+            ExpectedValue::try_new(540, 3),
+            Some(ExpectedValue::new(540, 3))
+        );
+        assert_eq!(ExpectedValue::try_new(0, 0), Some(ExpectedValue::default()));
+        assert_eq!(ExpectedValue::try_new(5, 0), None);
+    }
+
+    #[test]
+    fn eq() {
+        assert_eq!(ExpectedValue::new(10, 4), ExpectedValue::new(5, 2));
+        assert_ne!(ExpectedValue::new(10, 4), ExpectedValue::new(11, 4));
+        assert_eq!(ExpectedValue::new(0, 0), ExpectedValue::new(0, 7));
+    }
+
+    #[test]
+    fn ord() {
+        // Both truncate to 3
+        let a = ExpectedValue::new(10, 3);
+        let b = ExpectedValue::new(7, 2);
+
+        assert!(a < b);
+        assert_eq!(a.max(b), b);
+    }
+
+    #[test]
+    fn rounded() {
+        assert_eq!(ExpectedValue::new(10, 3).rounded(), 3);
+        assert_eq!(ExpectedValue::new(7, 2).rounded(), 4);
+        assert_eq!(ExpectedValue::new(11, 3).rounded(), 4);
+        assert_eq!(ExpectedValue::new(0, 0).rounded(), 0);
+        assert_eq!(ExpectedValue::new(7, 2).to_string(), "4");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let v = ExpectedValue::new(540, 3);
+        let json = serde_json::to_string(&v).unwrap();
+
+        assert_eq!(json, r#"{"total":540,"count":3}"#);
+        assert_eq!(serde_json::from_str::<ExpectedValue>(&json).unwrap(), v);
+        assert_eq!(
+            serde_json::from_str::<ExpectedValue>(r#"{"total":0,"count":0}"#).unwrap(),
+            ExpectedValue::default()
+        );
+        assert!(serde_json::from_str::<ExpectedValue>(r#"{"total":5,"count":0}"#).is_err());
+    }
+
+    #[test]
+    fn as_f64() {
+        assert_eq!(ExpectedValue::new(7, 2).as_f64(), 3.5);
+        assert_eq!(ExpectedValue::new(0, 0).as_f64(), 0.0);
+    }
+}
@@ -0,0 +1,360 @@
+pub use game_error::GameError;
+
+use super::board::history::BoardHistory;
+use super::solver::MAX_REVEALS;
+use super::{Board, BoardPosition, Edit, FillFailure, Line, PayoutTable, ValuedBoardPosition};
+
+mod game_error;
+
+/// Where a game's at.
+///
+/// Serialized by variant name, e.g. `"InitialReveal"`, `{"Scratching": {"remaining": 2}}` or
+/// `{"Resolved": {"line": "Row0", "payout": 10000}}`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Phase {
+    /// Waiting for the cell the game reveals for free
+    InitialReveal,
+    /// The player's scratching cells
+    Scratching { remaining: u8 },
+    /// Every scratch has been used; a line needs picking
+    LineSelection,
+    /// The line's been picked & paid out
+    Resolved { line: Line, payout: u16 },
+}
+
+/// A game of Mini Cactpot played by the rules: the game reveals one cell, the player scratches 3
+/// more & then picks a line, which gets paid out. Keeps the undo/redo history of the board.
+#[derive(Debug, Clone)]
+pub struct Game {
+    history: BoardHistory,
+    payouts: PayoutTable,
+    resolution: Option<(Line, u16)>,
+}
+
+impl Game {
+    #[inline]
+    pub fn new(payouts: PayoutTable) -> Self {
+        Self {
+            history: BoardHistory::default(),
+            payouts,
+            resolution: None,
+        }
+    }
+
+    /// Game picking up from a board revealed by the rules, i.e. with at most [`MAX_REVEALS`]
+    /// cells
+    #[inline]
+    pub(crate) fn from_board(board: Board, payouts: PayoutTable) -> Self {
+        debug_assert!(
+            board.len() <= MAX_REVEALS,
+            "Too many reveals: {}",
+            board.len()
+        );
+
+        Self {
+            history: BoardHistory::new(board),
+            payouts,
+            resolution: None,
+        }
+    }
+
+    #[inline]
+    pub fn board(&self) -> &Board {
+        self.history.board()
+    }
+
+    #[inline]
+    pub fn payouts(&self) -> &PayoutTable {
+        &self.payouts
+    }
+
+    /// Change the payouts, e.g. when the MGP bonus changes. Doesn't affect an already resolved
+    /// game.
+    #[inline]
+    pub fn set_payouts(&mut self, payouts: PayoutTable) {
+        self.payouts = payouts;
+    }
+
+    pub fn phase(&self) -> Phase {
+        if let Some((line, payout)) = self.resolution {
+            return Phase::Resolved { line, payout };
+        }
+
+        match self.board().len() {
+            0 => Phase::InitialReveal,
+            n if n < MAX_REVEALS => Phase::Scratching {
+                remaining: MAX_REVEALS - n,
+            },
+            _ => Phase::LineSelection,
+        }
+    }
+
+    /// Whether a cell can be revealed right now
+    #[inline]
+    pub fn can_reveal(&self) -> bool {
+        matches!(
+            self.phase(),
+            Phase::InitialReveal | Phase::Scratching { .. }
+        )
+    }
+
+    /// Record a revealed cell: the game's free one first, then the player's scratches
+    pub fn reveal(&mut self, pos: ValuedBoardPosition) -> Result<Phase, GameError> {
+        match self.phase() {
+            Phase::InitialReveal | Phase::Scratching { .. } => {}
+            Phase::LineSelection => return Err(GameError::NoScratchesLeft),
+            Phase::Resolved { .. } => return Err(GameError::AlreadyResolved),
+        }
+
+        self.history.fill(pos).map_err(GameError::Fill)?;
+
+        Ok(self.phase())
+    }
+
+    /// Clear a wrongly entered cell, returning what was in it
+    pub fn remove(&mut self, pos: BoardPosition) -> Result<ValuedBoardPosition, GameError> {
+        self.ensure_unresolved()?;
+
+        self.history
+            .remove(pos)
+            .ok_or(GameError::Fill(FillFailure::PositionNotFilled))
+    }
+
+    /// Correct the value of a wrongly entered cell, returning the previous one
+    pub fn replace(&mut self, pos: ValuedBoardPosition) -> Result<ValuedBoardPosition, GameError> {
+        self.ensure_unresolved()?;
+
+        self.history.replace(pos).map_err(GameError::Fill)
+    }
+
+    /// Whether there's an edit to [undo](Self::undo)
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        self.resolution.is_none() && self.history.can_undo()
+    }
+
+    /// Whether there's an edit to [redo](Self::redo)
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        self.resolution.is_none() && self.history.can_redo()
+    }
+
+    /// Take back the most recent edit to the board; `None` if there's nothing to undo
+    pub fn undo(&mut self) -> Result<Option<Edit>, GameError> {
+        self.ensure_unresolved()?;
+
+        Ok(self.history.undo())
+    }
+
+    /// Make the most recently undone edit again; `None` if there's nothing to redo
+    pub fn redo(&mut self) -> Result<Option<Edit>, GameError> {
+        self.ensure_unresolved()?;
+
+        Ok(self.history.redo())
+    }
+
+    #[inline]
+    fn ensure_unresolved(&self) -> Result<(), GameError> {
+        match self.resolution {
+            Some(_) => Err(GameError::AlreadyResolved),
+            None => Ok(()),
+        }
+    }
+
+    /// Pick a line, given its values once the game reveals them, ordered like
+    /// [`Line::positions`]. Returns the MGP won.
+    pub fn select_line(&mut self, line: Line, values: [u8; 3]) -> Result<u16, GameError> {
+        match self.phase() {
+            Phase::LineSelection => {}
+            Phase::InitialReveal => return Err(GameError::AwaitingInitialReveal),
+            Phase::Scratching { remaining } => return Err(GameError::ScratchesLeft(remaining)),
+            Phase::Resolved { .. } => return Err(GameError::AlreadyResolved),
+        }
+
+        let mut board = self.board().clone();
+        let mut sum = 0;
+        for (pos, value) in line.positions().iter().zip(values.iter()) {
+            match board.find(*pos) {
+                Some(revealed) if revealed.value() != *value => {
+                    return Err(GameError::LineMismatch)
+                }
+                Some(_) => {}
+                None => {
+                    let filled = ValuedBoardPosition::try_from_pos(*value, *pos)
+                        .map_err(|_| GameError::Fill(FillFailure::InvalidValue))?;
+                    board.fill(filled).map_err(|e| match e {
+                        FillFailure::ValueAlreadyContained => GameError::LineMismatch,
+                        e => GameError::Fill(e),
+                    })?
+                }
+            }
+            sum += value;
+        }
+
+        let payout = self.payouts.payout(sum);
+        self.resolution = Some((line, payout));
+
+        Ok(payout)
+    }
+
+    /// Start over with a fresh ticket
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.resolution = None;
+    }
+}
+
+impl Default for Game {
+    #[inline]
+    fn default() -> Self {
+        Self::new(PayoutTable::STANDARD)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn vbp(value: u8, col: u8, row: u8) -> ValuedBoardPosition {
+        ValuedBoardPosition::from_u8(value, col, row)
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_phase() {
+        let phases = [
+            (Phase::InitialReveal, r#""InitialReveal""#),
+            (
+                Phase::Scratching { remaining: 2 },
+                r#"{"Scratching":{"remaining":2}}"#,
+            ),
+            (
+                Phase::Resolved {
+                    line: Line::Row0,
+                    payout: 10000,
+                },
+                r#"{"Resolved":{"line":"Row0","payout":10000}}"#,
+            ),
+        ];
+        for (phase, json) in phases.iter() {
+            assert_eq!(serde_json::to_string(phase).unwrap(), *json);
+            assert_eq!(serde_json::from_str::<Phase>(json).unwrap(), *phase);
+        }
+    }
+
+    #[test]
+    fn phases() {
+        let mut game = Game::default();
+        assert_eq!(game.phase(), Phase::InitialReveal);
+
+        assert_eq!(
+            game.reveal(vbp(1, 0, 0)),
+            Ok(Phase::Scratching { remaining: 3 })
+        );
+        assert_eq!(
+            game.reveal(vbp(5, 1, 1)),
+            Ok(Phase::Scratching { remaining: 2 })
+        );
+        assert_eq!(
+            game.reveal(vbp(2, 1, 0)),
+            Ok(Phase::Scratching { remaining: 1 })
+        );
+        assert_eq!(game.reveal(vbp(9, 2, 2)), Ok(Phase::LineSelection));
+        assert!(!game.can_reveal());
+
+        assert_eq!(game.select_line(Line::Row0, [1, 2, 3]), Ok(10000));
+        assert_eq!(
+            game.phase(),
+            Phase::Resolved {
+                line: Line::Row0,
+                payout: 10000
+            }
+        );
+
+        game.reset();
+        assert_eq!(game.phase(), Phase::InitialReveal);
+        assert!(game.board().is_empty());
+    }
+
+    #[test]
+    fn illegal_moves() {
+        let mut game = Game::default();
+        assert_eq!(
+            game.select_line(Line::Row0, [1, 2, 3]),
+            Err(GameError::AwaitingInitialReveal)
+        );
+        assert_eq!(
+            game.reveal(vbp(0, 0, 0)),
+            Err(GameError::Fill(FillFailure::InvalidValue))
+        );
+
+        game.reveal(vbp(1, 0, 0)).unwrap();
+        assert_eq!(
+            game.reveal(vbp(1, 1, 0)),
+            Err(GameError::Fill(FillFailure::ValueAlreadyContained))
+        );
+        assert_eq!(
+            game.select_line(Line::Row0, [1, 2, 3]),
+            Err(GameError::ScratchesLeft(3))
+        );
+
+        game.reveal(vbp(5, 1, 1)).unwrap();
+        game.reveal(vbp(2, 1, 0)).unwrap();
+        game.reveal(vbp(9, 2, 2)).unwrap();
+        assert_eq!(game.reveal(vbp(3, 2, 0)), Err(GameError::NoScratchesLeft));
+
+        // Revealed cells have to match & new values can't repeat revealed ones
+        assert_eq!(
+            game.select_line(Line::Row0, [1, 4, 3]),
+            Err(GameError::LineMismatch)
+        );
+        assert_eq!(
+            game.select_line(Line::Row0, [1, 2, 9]),
+            Err(GameError::LineMismatch)
+        );
+        assert_eq!(
+            game.select_line(Line::Row0, [1, 2, 10]),
+            Err(GameError::Fill(FillFailure::InvalidValue))
+        );
+
+        assert_eq!(game.select_line(Line::Col2, [3, 4, 9]), Ok(72));
+        assert_eq!(
+            game.select_line(Line::Row0, [1, 2, 3]),
+            Err(GameError::AlreadyResolved)
+        );
+    }
+
+    #[test]
+    fn corrections() {
+        let mut game = Game::default();
+        game.reveal(vbp(1, 0, 0)).unwrap();
+        game.reveal(vbp(5, 1, 1)).unwrap();
+        game.reveal(vbp(2, 1, 0)).unwrap();
+        game.reveal(vbp(9, 2, 2)).unwrap();
+
+        assert_eq!(game.remove(BoardPosition::new(1, 0)), Ok(vbp(2, 1, 0)));
+        assert_eq!(game.phase(), Phase::Scratching { remaining: 1 });
+        assert_eq!(
+            game.remove(BoardPosition::new(1, 0)),
+            Err(GameError::Fill(FillFailure::PositionNotFilled))
+        );
+
+        assert!(game.can_undo());
+        assert_eq!(game.undo(), Ok(Some(Edit::Remove(vbp(2, 1, 0)))));
+        assert!(game.can_redo());
+        assert_eq!(game.phase(), Phase::LineSelection);
+        assert_eq!(game.replace(vbp(3, 1, 0)), Ok(vbp(2, 1, 0)));
+        assert_eq!(
+            game.replace(vbp(0, 1, 0)),
+            Err(GameError::Fill(FillFailure::InvalidValue))
+        );
+
+        game.select_line(Line::Row0, [1, 3, 2]).unwrap();
+        assert!(!game.can_undo());
+        assert_eq!(game.undo(), Err(GameError::AlreadyResolved));
+
+        game.reset();
+        assert!(!game.can_undo());
+    }
+}
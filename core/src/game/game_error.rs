@@ -0,0 +1,35 @@
+use std::fmt::{Display, Formatter};
+
+use crate::FillFailure;
+
+/// A move the rules don't allow at this point of the game
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GameError {
+    /// The reveal doesn't fit the board
+    Fill(FillFailure),
+    /// All the scratches have been used up; time to pick a line
+    NoScratchesLeft,
+    /// The game hasn't revealed its free cell yet, so there's nothing to pick a line from
+    AwaitingInitialReveal,
+    /// A line can only be picked once every scratch has been used
+    ScratchesLeft(u8),
+    /// The values given for the picked line contradict the board
+    LineMismatch,
+    /// The game's over
+    AlreadyResolved,
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fill(e) => write!(f, "Invalid reveal: {}", e),
+            Self::NoScratchesLeft => f.write_str("No scratches left"),
+            Self::AwaitingInitialReveal => f.write_str("The game hasn't revealed its cell yet"),
+            Self::ScratchesLeft(n) => write!(f, "{} scratch(es) left before picking a line", n),
+            Self::LineMismatch => f.write_str("Line values don't match the board"),
+            Self::AlreadyResolved => f.write_str("The game's already over"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
@@ -0,0 +1,98 @@
+use super::packed_board::PackedBoard;
+use super::recommendation::validate_board;
+use super::simulation::expected_value_from;
+use super::solver::{self, MAX_REVEALS};
+use super::{Board, BoardPosition, ExpectedValue, HeuristicStrategy, Objective, PayoutTable};
+
+/// What a ticket's worth from where it stands: the expected MGP of playing on from the current
+/// board.
+///
+/// Serialized as `{"optimal": .., "heuristic": ..}`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameValue {
+    optimal: ExpectedValue,
+    heuristic: ExpectedValue,
+}
+
+impl GameValue {
+    /// Game value under the [standard payouts](PayoutTable::STANDARD)
+    #[inline]
+    pub fn from_board(board: &Board) -> Result<GameValue, &'static str> {
+        Self::from_board_with(board, &PayoutTable::STANDARD)
+    }
+
+    pub fn from_board_with(
+        board: &Board,
+        payouts: &PayoutTable,
+    ) -> Result<GameValue, &'static str> {
+        validate_board(board)?;
+
+        let optimal = match board.len() < MAX_REVEALS {
+            true => {
+                let solution = solver::lookup_or_solve(board, payouts, Objective::ExpectedValue);
+                (0..9)
+                    .filter_map(|idx| solution.expected_value(BoardPosition::from_index(idx)))
+                    .max()
+            }
+            false => PackedBoard::from_board(board)
+                .line_stats()
+                .averages(payouts)
+                .iter()
+                .max()
+                .copied(),
+        };
+        let heuristic = expected_value_from(&mut HeuristicStrategy::new(*payouts), board, payouts)
+            .map_err(|_| "The heuristic couldn't finish the game")?;
+
+        Ok(GameValue {
+            optimal: optimal.unwrap_or_default(),
+            heuristic,
+        })
+    }
+
+    /// Expected MGP playing for the highest expected payout from here on
+    #[inline]
+    pub fn optimal(&self) -> ExpectedValue {
+        self.optimal
+    }
+
+    /// Expected MGP following the [heuristic](HeuristicStrategy) from here on
+    #[inline]
+    pub fn heuristic(&self) -> ExpectedValue {
+        self.heuristic
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Line, Recommendation};
+
+    #[test]
+    fn no_scratches_left() {
+        let board = "12_/6__/__9".parse::<Board>().unwrap();
+        let rec = Recommendation::from_board(&board).unwrap();
+        let best = Line::iter().map(|l| rec.line_value(l)).max().unwrap();
+        let value = GameValue::from_board(&board).unwrap();
+
+        assert_eq!(value.optimal(), best);
+        assert_eq!(value.heuristic(), best);
+    }
+
+    #[test]
+    fn optimal_beats_heuristic() {
+        let board = "7__/_4_/___".parse::<Board>().unwrap();
+        let rec = Recommendation::from_board(&board).unwrap();
+        let value = GameValue::from_board(&board).unwrap();
+
+        assert_eq!(value.optimal(), rec.ranked_reveals()[0].1);
+        assert!(value.optimal() >= value.heuristic());
+        assert!(value.heuristic() > ExpectedValue::default());
+    }
+
+    #[test]
+    fn needs_a_reveal() {
+        assert!(GameValue::from_board(&Board::default()).is_err());
+    }
+}
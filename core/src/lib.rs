@@ -2,9 +2,21 @@ pub use public_api::*;
 
 pub mod board;
 mod board_position;
-mod end_board;
 mod end_row;
+mod ending;
+mod expected_value;
+mod game;
+mod game_value;
+mod line;
+mod objective;
+mod packed_board;
 mod parsed_board;
 mod payouts;
+#[cfg(feature = "precomputed")]
+mod policy_table;
 mod public_api;
 mod recommendation;
+mod session;
+mod simulation;
+mod solver;
+mod strategy;
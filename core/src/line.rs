@@ -0,0 +1,246 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use super::{BoardPosition, ExpectedValue};
+
+pub mod distribution;
+pub mod line_map;
+pub mod stats;
+pub mod summary;
+
+pub const NUM_LINES: usize = 8;
+
+/// A line the payout can be picked for.
+///
+/// Serialized as the variant's name, e.g. `"Col0"` or `"TopLeftBottomRight"`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Line {
+    Col0,
+    Col1,
+    Col2,
+    Row0,
+    Row1,
+    Row2,
+    TopLeftBottomRight,
+    BottomLeftTopRight,
+}
+
+impl Line {
+    /// All the lines, ordered by [index](Self::index)
+    pub const ALL: [Line; NUM_LINES] = [
+        Line::Col0,
+        Line::Col1,
+        Line::Col2,
+        Line::Row0,
+        Line::Row1,
+        Line::Row2,
+        Line::TopLeftBottomRight,
+        Line::BottomLeftTopRight,
+    ];
+
+    /// Iterate over all the lines, ordered by [index](Self::index)
+    #[inline]
+    pub fn iter() -> impl Iterator<Item = Line> {
+        Self::ALL.iter().copied()
+    }
+
+    /// Column 0 to 2. Out of range input is only caught in debug builds; use
+    /// [`try_col`](Self::try_col) for anything untrusted.
+    #[inline]
+    pub fn col(idx: u8) -> Self {
+        debug_assert!(idx < 3, "Invalid col {}", idx);
+
+        Self::ALL[idx as usize]
+    }
+
+    /// Same as [`col`](Self::col), but `None` for anything past column 2
+    #[inline]
+    pub fn try_col(idx: u8) -> Option<Self> {
+        match idx < 3 {
+            true => Some(Self::col(idx)),
+            false => None,
+        }
+    }
+
+    /// Row 0 to 2. Out of range input is only caught in debug builds; use
+    /// [`try_row`](Self::try_row) for anything untrusted.
+    #[inline]
+    pub fn row(idx: u8) -> Self {
+        debug_assert!(idx < 3, "Invalid row {}", idx);
+
+        Self::ALL[idx as usize + 3]
+    }
+
+    /// Same as [`row`](Self::row), but `None` for anything past row 2
+    #[inline]
+    pub fn try_row(idx: u8) -> Option<Self> {
+        match idx < 3 {
+            true => Some(Self::row(idx)),
+            false => None,
+        }
+    }
+
+    /// Line at the given [index](Self::index). Out of range input is only caught in debug builds;
+    /// use [`try_from_index`](Self::try_from_index) for anything untrusted.
+    #[inline]
+    pub fn from_index(index: u8) -> Self {
+        debug_assert!((index as usize) < NUM_LINES, "Invalid line {}", index);
+
+        Self::ALL[index as usize]
+    }
+
+    /// Same as [`from_index`](Self::from_index), but `None` for an index with no line
+    #[inline]
+    pub fn try_from_index(index: u8) -> Option<Self> {
+        Self::ALL.get(index as usize).copied()
+    }
+
+    /// [Position indices](BoardPosition::index) of the cells each line goes through, ordered by
+    /// [line index](Self::index)
+    pub(crate) const CELLS: [[u8; 3]; NUM_LINES] = [
+        [0, 3, 6],
+        [1, 4, 7],
+        [2, 5, 8],
+        [0, 1, 2],
+        [3, 4, 5],
+        [6, 7, 8],
+        [0, 4, 8],
+        [6, 4, 2],
+    ];
+
+    /// Cells the line goes through
+    pub fn positions(&self) -> [BoardPosition; 3] {
+        let mut out = [BoardPosition::default(); 3];
+        for (pos, idx) in out
+            .iter_mut()
+            .zip(Self::CELLS[self.index() as usize].iter())
+        {
+            *pos = BoardPosition::from_index(*idx);
+        }
+
+        out
+    }
+
+    /// Whether the line goes through the given cell
+    #[inline]
+    pub fn contains(&self, pos: BoardPosition) -> bool {
+        Self::CELLS[self.index() as usize].contains(&pos.index())
+    }
+
+    #[inline]
+    pub fn is_diagonal(&self) -> bool {
+        matches!(self, Line::TopLeftBottomRight | Line::BottomLeftTopRight)
+    }
+
+    /// Columns first, then rows, then the top-left to bottom-right diagonal and finally the
+    /// bottom-left to top-right one
+    #[inline]
+    pub fn index(&self) -> u8 {
+        *self as u8
+    }
+}
+
+/// Lines sharing the best score, ties broken by the expected payout. Both arrays are ordered by
+/// [line index](Line::index).
+pub(crate) fn best_lines(
+    scores: &[ExpectedValue; NUM_LINES],
+    averages: &[ExpectedValue; NUM_LINES],
+) -> Vec<Line> {
+    let key = |line: &Line| {
+        let idx = line.index() as usize;
+        (scores[idx], averages[idx])
+    };
+    let best = match Line::ALL.iter().map(key).max() {
+        Some(v) => v,
+        None => return Vec::new(),
+    };
+
+    Line::ALL
+        .iter()
+        .filter(|l| key(l) == best)
+        .copied()
+        .collect()
+}
+
+impl Display for Line {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Line::Col0 => f.write_str("Column 0"),
+            Line::Col1 => f.write_str("Column 1"),
+            Line::Col2 => f.write_str("Column 2"),
+            Line::Row0 => f.write_str("Row 0"),
+            Line::Row1 => f.write_str("Row 1"),
+            Line::Row2 => f.write_str("Row 2"),
+            Line::TopLeftBottomRight => f.write_str("Top left to bottom right"),
+            Line::BottomLeftTopRight => f.write_str("Bottom left to top right"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn checked_constructors() {
+        assert_eq!(Line::try_col(2), Some(Line::Col2));
+        assert_eq!(Line::try_col(3), None);
+        assert_eq!(Line::try_row(0), Some(Line::Row0));
+        assert_eq!(Line::try_row(3), None);
+        assert_eq!(Line::try_from_index(7), Some(Line::BottomLeftTopRight));
+        assert_eq!(Line::try_from_index(8), None);
+    }
+
+    #[test]
+    fn index() {
+        for (i, line) in Line::ALL.iter().enumerate() {
+            assert_eq!(line.index() as usize, i, "{}", line);
+            assert_eq!(Line::from_index(i as u8), *line, "{}", line);
+        }
+    }
+
+    #[test]
+    fn col_row() {
+        assert_eq!(
+            [Line::col(0), Line::col(1), Line::col(2)],
+            [Line::Col0, Line::Col1, Line::Col2]
+        );
+        assert_eq!(
+            [Line::row(0), Line::row(1), Line::row(2)],
+            [Line::Row0, Line::Row1, Line::Row2]
+        );
+    }
+
+    #[test]
+    fn positions() {
+        let at = BoardPosition::new;
+
+        assert_eq!(Line::Col1.positions(), [at(1, 0), at(1, 1), at(1, 2)]);
+        assert_eq!(Line::Row2.positions(), [at(0, 2), at(1, 2), at(2, 2)]);
+        assert_eq!(
+            Line::BottomLeftTopRight.positions(),
+            [at(0, 2), at(1, 1), at(2, 0)]
+        );
+    }
+
+    #[test]
+    fn is_diagonal() {
+        let diagonals: Vec<Line> = Line::iter().filter(Line::is_diagonal).collect();
+
+        assert_eq!(
+            diagonals,
+            vec![Line::TopLeftBottomRight, Line::BottomLeftTopRight]
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let corner = BoardPosition::new(2, 0);
+
+        assert!(Line::Col2.contains(corner));
+        assert!(Line::Row0.contains(corner));
+        assert!(Line::BottomLeftTopRight.contains(corner));
+        assert!(!Line::TopLeftBottomRight.contains(corner));
+        assert!(!Line::Row1.contains(corner));
+    }
+}
@@ -0,0 +1,336 @@
+use super::stats::SumCounts;
+use crate::payouts::{PayoutTable, MAX_SUM, MIN_SUM, NUM_SUMS};
+
+/// Probability distribution of a line's point sum & payout across the endings of a board.
+///
+/// Serialized as `{"sum_counts": [..], "num_endings": .., "payouts": ..}`, where `sum_counts`
+/// holds the number of endings for every sum from 0 to 24. There must be at least one ending,
+/// & the counts must add up to `num_endings` with none below a sum of 6.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(into = "LineDistributionRepr", try_from = "LineDistributionRepr")
+)]
+pub struct LineDistribution {
+    sum_counts: SumCounts,
+    num_endings: u32,
+    payouts: PayoutTable,
+}
+
+/// Serialized form of a [`LineDistribution`]; see its docs for the fields
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LineDistributionRepr {
+    sum_counts: SumCounts,
+    num_endings: u32,
+    payouts: PayoutTable,
+}
+
+#[cfg(feature = "serde")]
+impl From<LineDistribution> for LineDistributionRepr {
+    fn from(dist: LineDistribution) -> Self {
+        Self {
+            sum_counts: dist.sum_counts,
+            num_endings: dist.num_endings,
+            payouts: dist.payouts,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<LineDistributionRepr> for LineDistribution {
+    type Error = &'static str;
+
+    fn try_from(repr: LineDistributionRepr) -> Result<Self, Self::Error> {
+        if repr.num_endings == 0 {
+            return Err("A line distribution needs at least one ending");
+        }
+        if repr.sum_counts[..MIN_SUM as usize].iter().any(|c| *c != 0) {
+            return Err("No line can add up to less than 6");
+        }
+        let total: u64 = repr.sum_counts.iter().map(|c| *c as u64).sum();
+        if total != repr.num_endings as u64 {
+            return Err("Sum counts must add up to the number of endings");
+        }
+
+        Ok(Self::from_counts(
+            repr.payouts,
+            repr.sum_counts,
+            repr.num_endings,
+        ))
+    }
+}
+
+impl LineDistribution {
+    #[inline]
+    pub(crate) fn from_counts(
+        payouts: PayoutTable,
+        sum_counts: SumCounts,
+        num_endings: u32,
+    ) -> Self {
+        Self {
+            sum_counts,
+            num_endings,
+            payouts,
+        }
+    }
+
+    #[inline]
+    fn probability(&self, count: u32) -> f64 {
+        count as f64 / self.num_endings as f64
+    }
+
+    /// Counts of each possible payout, lowest payout first
+    fn payout_counts(&self) -> Vec<(u16, u32)> {
+        let mut out: Vec<(u16, u32)> = Vec::with_capacity(NUM_SUMS);
+        for sum in MIN_SUM..=MAX_SUM {
+            let count = self.sum_counts[sum as usize];
+            if count == 0 {
+                continue;
+            }
+
+            let payout = self.payouts.payout(sum);
+            match out.iter_mut().find(|(p, _)| *p == payout) {
+                Some((_, c)) => *c += count,
+                None => out.push((payout, count)),
+            }
+        }
+        out.sort_unstable_by_key(|(payout, _)| *payout);
+
+        out
+    }
+
+    /// Number of endings the distribution was built from
+    #[inline]
+    pub fn num_endings(&self) -> u32 {
+        self.num_endings
+    }
+
+    /// Probability of the line adding up to the given sum
+    pub fn sum_probability(&self, sum: u8) -> f64 {
+        match sum > MAX_SUM {
+            true => 0.0,
+            false => self.probability(self.sum_counts[sum as usize]),
+        }
+    }
+
+    /// Probability of every sum from [`MIN_SUM`] to [`MAX_SUM`]
+    pub fn sum_probabilities(&self) -> impl Iterator<Item = (u8, f64)> + '_ {
+        (MIN_SUM..=MAX_SUM).map(move |sum| (sum, self.sum_probability(sum)))
+    }
+
+    /// Probability of the line paying out exactly the given amount
+    pub fn payout_probability(&self, payout: u16) -> f64 {
+        let count = (MIN_SUM..=MAX_SUM)
+            .filter(|sum| self.payouts.payout(*sum) == payout)
+            .map(|sum| self.sum_counts[sum as usize])
+            .sum();
+
+        self.probability(count)
+    }
+
+    /// Probability of every payout the line can still give, lowest payout first
+    pub fn payout_probabilities(&self) -> Vec<(u16, f64)> {
+        self.payout_counts()
+            .into_iter()
+            .map(|(payout, count)| (payout, self.probability(count)))
+            .collect()
+    }
+
+    /// Probability of the line paying out the given amount or more
+    pub fn probability_at_least(&self, payout: u16) -> f64 {
+        let count = (MIN_SUM..=MAX_SUM)
+            .filter(|sum| self.payouts.payout(*sum) >= payout)
+            .map(|sum| self.sum_counts[sum as usize])
+            .sum();
+
+        self.probability(count)
+    }
+
+    /// Expected payout
+    pub fn mean(&self) -> f64 {
+        let total: u64 = self
+            .payout_counts()
+            .iter()
+            .map(|(payout, count)| *payout as u64 * *count as u64)
+            .sum();
+
+        total as f64 / self.num_endings as f64
+    }
+
+    pub fn variance(&self) -> f64 {
+        let mean = self.mean();
+        let sq_total: f64 = self
+            .payout_counts()
+            .iter()
+            .map(|(payout, count)| {
+                let diff = *payout as f64 - mean;
+                diff * diff * *count as f64
+            })
+            .sum();
+
+        sq_total / self.num_endings as f64
+    }
+
+    #[inline]
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Lowest payout the line can still give
+    pub fn min(&self) -> u16 {
+        self.payout_counts().first().map(|(p, _)| *p).unwrap_or(0)
+    }
+
+    /// Highest payout the line can still give
+    pub fn max(&self) -> u16 {
+        self.payout_counts().last().map(|(p, _)| *p).unwrap_or(0)
+    }
+
+    /// Lowest payout that's at least as good as the given fraction (0..=1) of outcomes. Out of
+    /// range input is only caught in debug builds; use [`try_percentile`](Self::try_percentile)
+    /// for anything untrusted.
+    pub fn percentile(&self, fraction: f64) -> u16 {
+        debug_assert!(
+            (0.0..=1.0).contains(&fraction),
+            "Invalid fraction {}",
+            fraction
+        );
+
+        let threshold = fraction * self.num_endings as f64;
+        let mut cumulative = 0u32;
+        let counts = self.payout_counts();
+
+        for (payout, count) in counts.iter() {
+            cumulative += *count;
+            if cumulative as f64 >= threshold {
+                return *payout;
+            }
+        }
+
+        counts.last().map(|(p, _)| *p).unwrap_or(0)
+    }
+
+    /// Same as [`percentile`](Self::percentile), but `None` for a fraction outside 0..=1
+    #[inline]
+    pub fn try_percentile(&self, fraction: f64) -> Option<u16> {
+        match (0.0..=1.0).contains(&fraction) {
+            true => Some(self.percentile(fraction)),
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn mkdist_with(payouts: PayoutTable, sums: &[u8]) -> LineDistribution {
+        let mut counts = SumCounts::default();
+        for sum in sums {
+            counts[*sum as usize] += 1;
+        }
+
+        LineDistribution::from_counts(payouts, counts, sums.len() as u32)
+    }
+
+    fn mkdist(sums: &[u8]) -> LineDistribution {
+        mkdist_with(PayoutTable::STANDARD, sums)
+    }
+
+    #[test]
+    fn fixed_line() {
+        let dist = mkdist(&[6, 6, 6, 6]);
+
+        assert_eq!(dist.sum_probability(6), 1.0);
+        assert_eq!(dist.payout_probability(10000), 1.0);
+        assert_eq!(dist.payout_probabilities(), vec![(10000, 1.0)]);
+        assert_eq!(dist.mean(), 10000.0);
+        assert_eq!(dist.variance(), 0.0);
+        assert_eq!((dist.min(), dist.max()), (10000, 10000));
+        assert_eq!(dist.percentile(0.5), 10000);
+    }
+
+    #[test]
+    fn sum_probabilities() {
+        let dist = mkdist(&[7, 8, 8, 24]);
+        let probs: Vec<(u8, f64)> = dist.sum_probabilities().collect();
+
+        assert_eq!(probs.len(), 19);
+        assert_eq!(probs[0], (6, 0.0));
+        assert_eq!(probs[1], (7, 0.25));
+        assert_eq!(probs[2], (8, 0.5));
+        assert_eq!(probs[18], (24, 0.25));
+        assert_eq!(dist.sum_probability(25), 0.0);
+    }
+
+    #[test]
+    fn payouts_shared_between_sums() {
+        // 7 & 19 both pay 36
+        let dist = mkdist(&[7, 19, 20, 20]);
+
+        assert_eq!(dist.payout_probability(36), 0.5);
+        assert_eq!(dist.payout_probabilities(), vec![(36, 0.5), (306, 0.5)]);
+        assert_eq!((dist.min(), dist.max()), (36, 306));
+        assert_eq!(dist.probability_at_least(36), 1.0);
+        assert_eq!(dist.probability_at_least(37), 0.5);
+        assert_eq!(dist.probability_at_least(307), 0.0);
+    }
+
+    #[test]
+    fn stats() {
+        // Pays 36, 72, 306 & 3600
+        let dist = mkdist(&[7, 13, 20, 24]);
+        let mean = (36.0 + 72.0 + 306.0 + 3600.0) / 4.0;
+        let variance = [36.0, 72.0, 306.0, 3600.0]
+            .iter()
+            .map(|v: &f64| (v - mean) * (v - mean))
+            .sum::<f64>()
+            / 4.0;
+
+        assert_eq!(dist.mean(), mean);
+        assert!((dist.variance() - variance).abs() < 1e-6);
+        assert!((dist.std_dev() - variance.sqrt()).abs() < 1e-6);
+        assert_eq!(dist.percentile(0.0), 36);
+        assert_eq!(dist.percentile(0.25), 36);
+        assert_eq!(dist.percentile(0.5), 72);
+        assert_eq!(dist.percentile(0.75), 306);
+        assert_eq!(dist.percentile(1.0), 3600);
+        assert_eq!(dist.try_percentile(0.5), Some(72));
+        assert_eq!(dist.try_percentile(1.5), None);
+        assert_eq!(dist.try_percentile(f64::NAN), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let dist = mkdist(&[7, 13, 20, 24]);
+        let json = serde_json::to_string(&dist).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LineDistribution>(&json).unwrap(),
+            dist
+        );
+
+        let mut value = serde_json::to_value(&dist).unwrap();
+        value["num_endings"] = 5.into();
+        assert!(serde_json::from_value::<LineDistribution>(value).is_err());
+
+        let empty = serde_json::to_value(LineDistribution::default()).unwrap();
+        assert!(serde_json::from_value::<LineDistribution>(empty).is_err());
+
+        let mut value = serde_json::to_value(mkdist(&[7])).unwrap();
+        value["sum_counts"][7] = 0.into();
+        value["sum_counts"][3] = 1.into();
+        assert!(serde_json::from_value::<LineDistribution>(value).is_err());
+    }
+
+    #[test]
+    fn custom_payouts() {
+        let payouts = PayoutTable::new([7; NUM_SUMS]);
+        let dist = mkdist_with(payouts, &[6, 24]);
+
+        assert_eq!(dist.payout_probabilities(), vec![(7, 1.0)]);
+        assert_eq!(dist.mean(), 7.0);
+    }
+}
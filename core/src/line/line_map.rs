@@ -0,0 +1,68 @@
+use std::ops::{Index, IndexMut};
+
+use super::{Line, NUM_LINES};
+
+/// A value for each of the 8 lines.
+///
+/// Serialized as an array of the 8 values ordered by [line index](Line::index).
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct LineMap<T>([T; NUM_LINES]);
+
+impl<T> LineMap<T> {
+    /// Map from values ordered by [line index](Line::index)
+    #[inline]
+    pub fn from_array(values: [T; NUM_LINES]) -> Self {
+        Self(values)
+    }
+
+    #[inline]
+    pub fn get(&self, line: Line) -> &T {
+        &self.0[line.index() as usize]
+    }
+
+    /// Every line along with its value, ordered by [line index](Line::index)
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Line, &T)> {
+        Line::iter().zip(self.0.iter())
+    }
+
+    /// Values ordered by [line index](Line::index)
+    #[inline]
+    pub fn values(&self) -> &[T; NUM_LINES] {
+        &self.0
+    }
+}
+
+impl<T> Index<Line> for LineMap<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, line: Line) -> &Self::Output {
+        self.get(line)
+    }
+}
+
+impl<T> IndexMut<Line> for LineMap<T> {
+    #[inline]
+    fn index_mut(&mut self, line: Line) -> &mut Self::Output {
+        &mut self.0[line.index() as usize]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keyed_by_line() {
+        let mut map = LineMap::from_array([0, 1, 2, 3, 4, 5, 6, 7]);
+        map[Line::Row1] = 40;
+
+        assert_eq!(map[Line::Col2], 2);
+        assert_eq!(*map.get(Line::Row1), 40);
+        assert_eq!(map.iter().last(), Some((Line::BottomLeftTopRight, &7)));
+        assert_eq!(map.iter().count(), NUM_LINES);
+    }
+}
@@ -0,0 +1,152 @@
+use super::distribution::LineDistribution;
+use super::{Line, NUM_LINES};
+use crate::objective::Scorer;
+use crate::payouts::{PayoutTable, MAX_SUM};
+use crate::ExpectedValue;
+
+pub type SumCounts = [u32; MAX_SUM as usize + 1];
+
+/// How often each line adds up to each sum across a set of endings
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct LineStats {
+    sum_counts: [SumCounts; NUM_LINES],
+    num_endings: u32,
+}
+
+impl LineStats {
+    /// Count an ending, given as the value of each cell in position index order
+    #[inline]
+    pub fn add(&mut self, cells: &[u8; 9]) {
+        for (counts, line) in self.sum_counts.iter_mut().zip(Line::CELLS.iter()) {
+            let sum = cells[line[0] as usize] + cells[line[1] as usize] + cells[line[2] as usize];
+            counts[sum as usize] += 1;
+        }
+        self.num_endings += 1;
+    }
+
+    /// Stop counting an ending that was previously [added](Self::add)
+    #[inline]
+    pub fn remove(&mut self, cells: &[u8; 9]) {
+        for (counts, line) in self.sum_counts.iter_mut().zip(Line::CELLS.iter()) {
+            let sum = cells[line[0] as usize] + cells[line[1] as usize] + cells[line[2] as usize];
+            counts[sum as usize] -= 1;
+        }
+        self.num_endings -= 1;
+    }
+
+    /// Stats across the endings of both
+    #[cfg(feature = "parallel")]
+    pub fn merge(mut self, other: &LineStats) -> Self {
+        for (counts, other_counts) in self.sum_counts.iter_mut().zip(other.sum_counts.iter()) {
+            for (count, other_count) in counts.iter_mut().zip(other_counts.iter()) {
+                *count += *other_count;
+            }
+        }
+        self.num_endings += other.num_endings;
+
+        self
+    }
+
+    /// Sum counts of each line, ordered by [line index](Line::index)
+    #[inline]
+    pub fn sum_counts(&self) -> &[SumCounts; NUM_LINES] {
+        &self.sum_counts
+    }
+
+    /// Per line totals of `score(sum)` across every ending
+    fn totals<F: Fn(u8) -> u64>(&self, score: F) -> [ExpectedValue; NUM_LINES] {
+        let mut out = [ExpectedValue::default(); NUM_LINES];
+        for (value, counts) in out.iter_mut().zip(self.sum_counts.iter()) {
+            let total = counts
+                .iter()
+                .enumerate()
+                .map(|(sum, count)| *count as u64 * score(sum as u8))
+                .sum();
+            *value = ExpectedValue::new(total, self.num_endings as u64);
+        }
+
+        out
+    }
+
+    /// Expected payout of each line, ordered by [line index](Line::index)
+    pub fn averages(&self, payouts: &PayoutTable) -> [ExpectedValue; NUM_LINES] {
+        self.totals(|sum| payouts.payout(sum) as u64)
+    }
+
+    /// [Objective](crate::Objective) score of each line, ordered by [line index](Line::index)
+    pub fn scores(&self, payouts: &PayoutTable, scorer: &Scorer) -> [ExpectedValue; NUM_LINES] {
+        let mut out = [ExpectedValue::default(); NUM_LINES];
+        for (value, counts) in out.iter_mut().zip(self.sum_counts.iter()) {
+            let payout_counts = counts
+                .iter()
+                .enumerate()
+                .map(|(sum, count)| (payouts.payout(sum as u8), *count as u64));
+            *value = ExpectedValue::new(scorer.line_total(payout_counts), self.num_endings as u64);
+        }
+
+        out
+    }
+
+    /// Payout distribution of each line, ordered by [line index](Line::index)
+    pub fn distributions(&self, payouts: &PayoutTable) -> [LineDistribution; NUM_LINES] {
+        let mut out: [LineDistribution; NUM_LINES] = Default::default();
+        for (dist, counts) in out.iter_mut().zip(self.sum_counts.iter()) {
+            *dist = LineDistribution::from_counts(*payouts, *counts, self.num_endings);
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Objective;
+
+    #[test]
+    fn add() {
+        let mut stats = LineStats::default();
+        stats.add(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        stats.add(&[9, 8, 7, 6, 5, 4, 3, 2, 1]);
+
+        assert_eq!(stats.num_endings, 2);
+        // 1 + 4 + 7 & 9 + 6 + 3
+        assert_eq!(stats.sum_counts()[Line::Col0.index() as usize][12], 1);
+        assert_eq!(stats.sum_counts()[Line::Col0.index() as usize][18], 1);
+        // 1 + 5 + 9 both times
+        assert_eq!(
+            stats.sum_counts()[Line::TopLeftBottomRight.index() as usize][15],
+            2
+        );
+
+        let payouts = PayoutTable::STANDARD;
+        let averages = stats.averages(&payouts);
+        assert_eq!(
+            averages[Line::Row0.index() as usize],
+            ExpectedValue::new(10000 + 3600, 2)
+        );
+
+        let scores = stats.scores(&payouts, &Objective::Jackpot.scorer(&payouts));
+        assert_eq!(
+            scores[Line::Row0.index() as usize],
+            ExpectedValue::new(1, 2)
+        );
+        assert_eq!(
+            scores[Line::Col0.index() as usize],
+            ExpectedValue::new(0, 2)
+        );
+    }
+
+    #[test]
+    fn remove() {
+        let mut stats = LineStats::default();
+        stats.add(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let once = stats.clone();
+        stats.add(&[9, 8, 7, 6, 5, 4, 3, 2, 1]);
+        stats.remove(&[9, 8, 7, 6, 5, 4, 3, 2, 1]);
+
+        assert_eq!(stats, once);
+        stats.remove(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(stats, LineStats::default());
+    }
+}
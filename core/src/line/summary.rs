@@ -0,0 +1,46 @@
+use super::distribution::LineDistribution;
+use crate::ExpectedValue;
+
+/// How a line is looking on the current board.
+///
+/// Serialized as `{"value": .., "score": .., "distribution": ..}`.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineSummary {
+    value: ExpectedValue,
+    score: ExpectedValue,
+    distribution: LineDistribution,
+}
+
+impl LineSummary {
+    #[inline]
+    pub(crate) fn new(
+        value: ExpectedValue,
+        score: ExpectedValue,
+        distribution: LineDistribution,
+    ) -> Self {
+        Self {
+            value,
+            score,
+            distribution,
+        }
+    }
+
+    /// Expected payout of picking the line
+    #[inline]
+    pub fn value(&self) -> ExpectedValue {
+        self.value
+    }
+
+    /// [Objective](crate::Objective) score of picking the line
+    #[inline]
+    pub fn score(&self) -> ExpectedValue {
+        self.score
+    }
+
+    /// Payout distribution of the line across the board's possible endings
+    #[inline]
+    pub fn distribution(&self) -> &LineDistribution {
+        &self.distribution
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use super::PayoutTable;
+
+/// What the suggested cells & lines should be optimised for.
+///
+/// Serialized as `"ExpectedValue"`, `"Jackpot"`, `{"AtLeast": 1080}` or `"MinVariance"`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Objective {
+    /// Highest expected payout
+    ExpectedValue,
+    /// Highest chance of winning the top payout of the table
+    Jackpot,
+    /// Highest chance of winning at least the given amount of MGP
+    AtLeast(u16),
+    /// Lowest variance of the payout, the expected payout breaking ties. Scratches go for the
+    /// lowest expected variance of the line picked once they've been used up.
+    ///
+    /// Scores are [`VARIANCE_CEILING`] minus the variance so that higher is still better.
+    MinVariance,
+}
+
+/// No line's payout variance can get past this: payouts are `u16`s, so the furthest they can
+/// spread is `(u16::MAX / 2)²`
+pub const VARIANCE_CEILING: u64 = 1 << 30;
+
+impl Objective {
+    /// Lowest payout counted as a win; `None` when going for the highest expected payout
+    pub fn threshold(&self, payouts: &PayoutTable) -> Option<u16> {
+        match self {
+            Self::ExpectedValue | Self::MinVariance => None,
+            Self::Jackpot => Some(payouts.max_payout()),
+            Self::AtLeast(v) => Some(*v),
+        }
+    }
+
+    pub(crate) fn scorer(&self, payouts: &PayoutTable) -> Scorer {
+        match self {
+            Self::MinVariance => Scorer::Variance,
+            _ => match self.threshold(payouts) {
+                None => Scorer::Payout,
+                Some(threshold) => Scorer::Threshold(threshold),
+            },
+        }
+    }
+}
+
+impl Default for Objective {
+    #[inline]
+    fn default() -> Self {
+        Self::ExpectedValue
+    }
+}
+
+impl Display for Objective {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::ExpectedValue => f.write_str("Expected value"),
+            Self::Jackpot => f.write_str("Jackpot chance"),
+            Self::AtLeast(v) => write!(f, "Chance of at least {} MGP", v),
+            Self::MinVariance => f.write_str("Lowest variance"),
+        }
+    }
+}
+
+/// An [`Objective`] resolved against a payout table
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Scorer {
+    /// The payout itself
+    Payout,
+    /// 1 for every payout of at least the threshold
+    Threshold(u16),
+    /// [`VARIANCE_CEILING`] minus the variance of the payouts
+    Variance,
+}
+
+impl Scorer {
+    /// Score total of a line across a set of endings, given as the number of endings paying out
+    /// each amount. The variance is rounded down to a whole MGP².
+    pub fn line_total<I: IntoIterator<Item = (u16, u64)>>(&self, payout_counts: I) -> u64 {
+        let payout_counts = payout_counts.into_iter();
+        match self {
+            Self::Payout => payout_counts.map(|(p, count)| p as u64 * count).sum(),
+            Self::Threshold(threshold) => payout_counts
+                .filter(|(p, _)| p >= threshold)
+                .map(|(_, count)| count)
+                .sum(),
+            Self::Variance => {
+                let (mut num, mut total, mut sq_total) = (0u128, 0u128, 0u128);
+                for (p, count) in payout_counts {
+                    let (p, count) = (p as u128, count as u128);
+                    num += count;
+                    total += p * count;
+                    sq_total += p * p * count;
+                }
+                if num == 0 {
+                    return 0;
+                }
+
+                // Sum of the squared deviations from the mean
+                let sq_dev = (num * sq_total - total * total) / num;
+
+                (num * VARIANCE_CEILING as u128 - sq_dev) as u64
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn threshold() {
+        let payouts = PayoutTable::STANDARD;
+
+        assert_eq!(Objective::ExpectedValue.threshold(&payouts), None);
+        assert_eq!(Objective::Jackpot.threshold(&payouts), Some(10000));
+        assert_eq!(
+            Objective::Jackpot.threshold(&payouts.with_bonus(10)),
+            Some(11000)
+        );
+        assert_eq!(Objective::AtLeast(720).threshold(&payouts), Some(720));
+    }
+
+    #[test]
+    fn score() {
+        let payouts = PayoutTable::STANDARD;
+        let score =
+            |objective: Objective, payout| objective.scorer(&payouts).line_total(vec![(payout, 1)]);
+
+        assert_eq!(score(Objective::ExpectedValue, 72), 72);
+        assert_eq!(score(Objective::Jackpot, 3600), 0);
+        assert_eq!(score(Objective::Jackpot, 10000), 1);
+        assert_eq!(score(Objective::AtLeast(720), 720), 1);
+        assert_eq!(score(Objective::AtLeast(720), 360), 0);
+        assert_eq!(score(Objective::MinVariance, 360), VARIANCE_CEILING);
+    }
+
+    #[test]
+    fn line_total() {
+        let payouts = PayoutTable::STANDARD;
+        let counts = vec![(36, 3), (72, 1), (10000, 0)];
+        let total = |objective: Objective| objective.scorer(&payouts).line_total(counts.clone());
+
+        assert_eq!(total(Objective::ExpectedValue), 180);
+        assert_eq!(total(Objective::AtLeast(72)), 1);
+        assert_eq!(total(Objective::Jackpot), 0);
+        // Mean 45, squared deviations 3 * 81 + 729
+        assert_eq!(total(Objective::MinVariance), 4 * VARIANCE_CEILING - 972);
+        assert_eq!(
+            Objective::MinVariance
+                .scorer(&payouts)
+                .line_total(vec![(u16::MAX, 1), (0, 1)]),
+            // Squared deviations of 2 * 32767.5², rounded down
+            2 * VARIANCE_CEILING - 2 * 32767 * 32768
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use super::line::stats::LineStats;
+use super::{Board, BoardPosition};
+
+const NUM_CELLS: u8 = 9;
+
+/// Bits 1 to 9 set
+const ALL_VALUES: u16 = 0b11_1111_1110;
+
+/// Boards with fewer fills than this get their endings enumerated in parallel
+#[cfg(feature = "parallel")]
+const PARALLEL_BELOW_FILLS: u32 = 2;
+
+/// A board packed into an integer, 4 bits per cell in [position index](BoardPosition::index)
+/// order, 0 meaning unrevealed. Cheap to copy, hash & compare.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct PackedBoard(u64);
+
+impl PackedBoard {
+    pub fn from_board(board: &Board) -> Self {
+        (0..NUM_CELLS).fold(Self::default(), |packed, idx| {
+            match board.find(BoardPosition::from_index(idx)) {
+                Some(vbp) => packed.with(idx, vbp.value()),
+                None => packed,
+            }
+        })
+    }
+
+    /// Board from the value of each cell in position index order, 0 meaning unrevealed
+    pub fn from_cells(cells: &[u8; NUM_CELLS as usize]) -> Self {
+        (0..NUM_CELLS).fold(Self::default(), |packed, idx| match cells[idx as usize] {
+            0 => packed,
+            value => packed.with(idx, value),
+        })
+    }
+
+    #[inline]
+    pub fn value_at(self, pos_index: u8) -> u8 {
+        ((self.0 >> (pos_index * 4)) & 0xF) as u8
+    }
+
+    /// The same board with the given cell revealed
+    #[inline]
+    pub fn with(self, pos_index: u8, value: u8) -> Self {
+        debug_assert_eq!(
+            self.value_at(pos_index),
+            0,
+            "Cell {} already filled",
+            pos_index
+        );
+
+        Self(self.0 | ((value as u64) << (pos_index * 4)))
+    }
+
+    /// Revealed values as a bit mask, bit `n` being set if `n` has been revealed
+    #[inline]
+    pub fn used_values(self) -> u16 {
+        (0..NUM_CELLS).fold(0, |mask, idx| mask | (1 << self.value_at(idx))) & ALL_VALUES
+    }
+
+    /// Value of each cell, 0 if unrevealed
+    pub fn cells(self) -> [u8; NUM_CELLS as usize] {
+        let mut out = [0; NUM_CELLS as usize];
+        for (idx, cell) in out.iter_mut().enumerate() {
+            *cell = self.value_at(idx as u8);
+        }
+
+        out
+    }
+
+    /// Call `f` with the cells of every possible ending of the board without allocating. Empty
+    /// cells get filled in position index order, lowest value first.
+    pub fn for_each_ending<F: FnMut(&[u8; NUM_CELLS as usize])>(self, mut f: F) {
+        let mut cells = self.cells();
+        let mut empty = [0u8; NUM_CELLS as usize];
+        let mut num_empty = 0;
+        for (idx, value) in cells.iter().enumerate() {
+            if *value == 0 {
+                empty[num_empty] = idx as u8;
+                num_empty += 1;
+            }
+        }
+
+        fill_endings(
+            &mut cells,
+            &empty[..num_empty],
+            ALL_VALUES & !self.used_values(),
+            &mut f,
+        );
+    }
+
+    /// Line sums across every possible ending of the board
+    pub fn line_stats(self) -> LineStats {
+        #[cfg(feature = "parallel")]
+        {
+            if self.used_values().count_ones() < PARALLEL_BELOW_FILLS {
+                return self.parallel_line_stats();
+            }
+        }
+
+        self.serial_line_stats()
+    }
+
+    fn serial_line_stats(self) -> LineStats {
+        let mut stats = LineStats::default();
+        self.for_each_ending(|cells| stats.add(cells));
+
+        stats
+    }
+
+    /// Splits the enumeration on the values of the first empty cell
+    #[cfg(feature = "parallel")]
+    fn parallel_line_stats(self) -> LineStats {
+        use rayon::prelude::*;
+
+        let idx = match (0..NUM_CELLS).find(|idx| self.value_at(*idx) == 0) {
+            Some(v) => v,
+            None => return self.serial_line_stats(),
+        };
+        let used = self.used_values();
+
+        (1..=NUM_CELLS)
+            .into_par_iter()
+            .filter(|value| used & (1 << value) == 0)
+            .map(|value| self.with(idx, value).serial_line_stats())
+            .reduce(LineStats::default, |a, b| a.merge(&b))
+    }
+}
+
+fn fill_endings<F: FnMut(&[u8; NUM_CELLS as usize])>(
+    cells: &mut [u8; NUM_CELLS as usize],
+    empty: &[u8],
+    available: u16,
+    f: &mut F,
+) {
+    let (idx, rest) = match empty.split_first() {
+        Some(v) => v,
+        None => return f(cells),
+    };
+
+    let mut remaining = available;
+    while remaining != 0 {
+        let value = remaining.trailing_zeros() as u8;
+        remaining &= remaining - 1;
+
+        cells[*idx as usize] = value;
+        fill_endings(cells, rest, available & !(1 << value), f);
+    }
+    cells[*idx as usize] = 0;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let board = "_8_/7__/__3".parse::<Board>().unwrap();
+        let packed = PackedBoard::from_board(&board);
+
+        assert_eq!(packed.value_at(8), 3);
+        assert_eq!(packed.value_at(0), 0);
+        assert_eq!(packed.used_values(), (1 << 3) | (1 << 7) | (1 << 8));
+        assert_eq!(packed.with(0, 1).value_at(0), 1);
+    }
+
+    #[test]
+    fn endings() {
+        let packed = PackedBoard::from_board(&"_8_/7__/__3".parse::<Board>().unwrap());
+        let mut endings = Vec::new();
+        packed.for_each_ending(|cells| endings.push(*cells));
+
+        assert_eq!(endings.len(), 720);
+        assert_eq!(endings[0], [1, 8, 2, 7, 4, 5, 6, 9, 3]);
+        for cells in endings.iter() {
+            let mut sorted = *cells;
+            sorted.sort_unstable();
+            assert_eq!(sorted, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+            assert_eq!((cells[1], cells[3], cells[8]), (8, 7, 3));
+        }
+        for pair in endings.windows(2) {
+            assert!(pair[0] < pair[1]);
+        }
+    }
+
+    #[test]
+    fn full_board() {
+        let board = "123/456/789".parse::<Board>().unwrap();
+        let mut count = 0;
+        PackedBoard::from_board(&board).for_each_ending(|cells| {
+            assert_eq!(cells, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+            count += 1;
+        });
+
+        assert_eq!(count, 1);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_line_stats() {
+        let boards = [
+            "___/___/___".parse::<Board>().unwrap(),
+            "___/_5_/___".parse::<Board>().unwrap(),
+            "__1/___/9__".parse::<Board>().unwrap(),
+            "_8_/7__/__3".parse::<Board>().unwrap(),
+        ];
+
+        for board in boards.iter() {
+            let packed = PackedBoard::from_board(board);
+            assert_eq!(packed.parallel_line_stats(), packed.serial_line_stats());
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use std::cmp::Ordering;
 
 use super::end_row::EndRow;
-use super::Board;
+use super::line::NUM_LINES;
+use super::{Board, PayoutTable};
 
-pub type Rows = [EndRow; 8];
+pub type Rows = [EndRow; NUM_LINES];
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ParsedBoardPayouts {
@@ -47,52 +48,53 @@ impl Ord for ParsedBoard {
 }
 
 impl ParsedBoard {
-    pub fn from_board(board: Board) -> Self {
-        let r1 = board.row(0);
-        let r2 = board.row(1);
-        let r3 = board.row(2);
+    pub fn from_board(board: Board, payouts: &PayoutTable) -> Self {
+        let end_rows = Self::end_rows(&board);
+        let [r0, r1, r2, c0, c1, c2, bl_tr, tl_br] = &end_rows;
+        let line_payouts =
+            [c0, c1, c2, r0, r1, r2, tl_br, bl_tr].map(|row| payouts.payout(row.point_sum()));
 
-        let c1 = board.col(0);
-        let c2 = board.col(1);
-        let c3 = board.col(2);
+        Self::build(board, end_rows, line_payouts)
+    }
+
+    /// Full board paying out the given amounts, ordered by [line index](crate::Line::index)
+    #[cfg(feature = "serde")]
+    pub fn from_line_payouts(board: Board, line_payouts: [u16; NUM_LINES]) -> Self {
+        let end_rows = Self::end_rows(&board);
 
-        let bl_tr = board.diag_bl_tr();
-        let tl_br = board.diag_tl_br();
+        Self::build(board, end_rows, line_payouts)
+    }
 
-        let payouts_row = [r1.payout_value(), r2.payout_value(), r3.payout_value()];
-        let payouts_col = [c1.payout_value(), c2.payout_value(), c3.payout_value()];
-        let payout_bl_tr = bl_tr.payout_value();
-        let payout_tl_br = tl_br.payout_value();
+    fn end_rows(board: &Board) -> Rows {
+        [
+            board.row(0),
+            board.row(1),
+            board.row(2),
+            board.col(0),
+            board.col(1),
+            board.col(2),
+            board.diag_bl_tr(),
+            board.diag_tl_br(),
+        ]
+    }
 
-        let end_rows = [r1, r2, r3, c1, c2, c3, bl_tr, tl_br];
+    fn build(board: Board, end_rows: Rows, line_payouts: [u16; NUM_LINES]) -> Self {
+        let [c0, c1, c2, r0, r1, r2, payout_tl_br, payout_bl_tr] = line_payouts;
 
         let payouts_board = {
-            let mut min: u16 = u16::MAX;
-            let mut max = u16::MIN;
-            let avg = {
-                let mut total = 0u16;
-
-                for row in end_rows.iter() {
-                    let v = row.payout_value();
-                    if v < min {
-                        min = v;
-                    }
-                    if v > max {
-                        max = v;
-                    }
-                    total += v;
-                }
-
-                total / (end_rows.len() as u16)
-            };
+            let min = line_payouts.iter().copied().min().unwrap_or_default();
+            let max = line_payouts.iter().copied().max().unwrap_or_default();
+            // 8 line payouts of up to u16::MAX each don't fit in a u16
+            let total: u32 = line_payouts.iter().map(|v| *v as u32).sum();
+            let avg = (total / NUM_LINES as u32) as u16;
 
             ParsedBoardPayouts { avg, max, min }
         };
 
         ParsedBoard {
             payouts_board,
-            payouts_row,
-            payouts_col,
+            payouts_row: [r0, r1, r2],
+            payouts_col: [c0, c1, c2],
             payout_bl_tr,
             payout_tl_br,
             board,
@@ -100,7 +102,62 @@ impl ParsedBoard {
         }
     }
 
-    pub fn end_rows(&self) -> &Rows {
-        &self.end_rows
+    #[inline]
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    #[inline]
+    pub fn average_payout(&self) -> u16 {
+        self.payouts_board.avg
+    }
+
+    #[inline]
+    pub fn max_payout(&self) -> u16 {
+        self.payouts_board.max
+    }
+
+    #[inline]
+    pub fn min_payout(&self) -> u16 {
+        self.payouts_board.min
+    }
+
+    /// Line sums ordered by [line index](crate::Line::index)
+    pub fn line_sums(&self) -> [u8; NUM_LINES] {
+        let [r0, r1, r2, c0, c1, c2, bl_tr, tl_br] = &self.end_rows;
+
+        [c0, c1, c2, r0, r1, r2, tl_br, bl_tr].map(EndRow::point_sum)
+    }
+
+    /// Line payouts ordered by [line index](crate::Line::index)
+    pub fn line_payouts(&self) -> [u16; NUM_LINES] {
+        let [c0, c1, c2] = self.payouts_col;
+        let [r0, r1, r2] = self.payouts_row;
+
+        [c0, c1, c2, r0, r1, r2, self.payout_tl_br, self.payout_bl_tr]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::payouts::NUM_SUMS;
+
+    #[test]
+    fn large_payouts() {
+        let board: Board = "123/456/789".parse().unwrap();
+
+        let parsed = ParsedBoard::from_board(board.clone(), &PayoutTable::STANDARD.with_bonus(500));
+        let payouts = parsed.line_payouts();
+        let total: u32 = payouts.iter().map(|v| *v as u32).sum();
+        assert_eq!(parsed.average_payout() as u32, total / 8);
+        assert_eq!(parsed.max_payout(), *payouts.iter().max().unwrap());
+
+        let table = PayoutTable::new([u16::MAX; NUM_SUMS]);
+        let parsed = ParsedBoard::from_board(board.clone(), &table);
+        assert_eq!(parsed.average_payout(), u16::MAX);
+        assert_eq!(parsed.min_payout(), u16::MAX);
+
+        assert_eq!(board.endings(&table).count(), 1);
     }
 }
@@ -1,14 +1,195 @@
-const PAYOUTS_ARRAY: [u16; 25] = [
-    0u16, 0, 0, 0, 0, 0, 10000, 36, 720, 360, 80, 252, 108, 72, 54, 180, 72, 180, 119, 36, 306,
-    1080, 144, 1800, 3600,
-];
+use std::fmt::{Display, Formatter};
+#[cfg(any(feature = "toml", feature = "json"))]
+use std::path::Path;
 
+/// Lowest possible line sum: 1 + 2 + 3
+pub const MIN_SUM: u8 = 6;
+
+/// Highest possible line sum: 7 + 8 + 9
+pub const MAX_SUM: u8 = 24;
+
+/// Number of distinct line sums
+pub const NUM_SUMS: usize = (MAX_SUM - MIN_SUM + 1) as usize;
+
+/// Payout for the given points under the [standard table](PayoutTable::STANDARD)
+#[cfg(test)]
 #[inline]
-pub fn payout_for_points(points: u8) -> u16 {
-    if points > 24 {
-        0
-    } else {
-        PAYOUTS_ARRAY[points as usize]
+pub(crate) fn payout_for_points(points: u8) -> u16 {
+    PayoutTable::STANDARD.payout(points)
+}
+
+/// MGP paid out for each line sum.
+///
+/// Serialized as `{"payouts": [..], "bonus": 0}`, with a payout for every sum from 6 to 24.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PayoutTable {
+    payouts: [u16; NUM_SUMS],
+    bonus: u16,
+}
+
+#[derive(Debug)]
+pub enum PayoutTableError {
+    /// A payout must be given for every sum from 6 to 24; contains the number of payouts given
+    InvalidLength(usize),
+    #[cfg(any(feature = "toml", feature = "json"))]
+    Io(std::io::Error),
+    #[cfg(feature = "toml")]
+    Parse(toml::de::Error),
+    #[cfg(feature = "json")]
+    ParseJson(serde_json::Error),
+}
+
+impl Display for PayoutTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLength(len) => write!(
+                f,
+                "Expected {} payouts, one for every sum from {} to {}, but got {}",
+                NUM_SUMS, MIN_SUM, MAX_SUM, len
+            ),
+            #[cfg(any(feature = "toml", feature = "json"))]
+            Self::Io(e) => write!(f, "Failed to read payout table: {}", e),
+            #[cfg(feature = "toml")]
+            Self::Parse(e) => write!(f, "Failed to parse payout table: {}", e),
+            #[cfg(feature = "json")]
+            Self::ParseJson(e) => write!(f, "Failed to parse payout table: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PayoutTableError {}
+
+/// On-disk format of a payout table
+#[cfg(any(feature = "toml", feature = "json"))]
+#[derive(serde::Deserialize)]
+struct PayoutTableFile {
+    payouts: Vec<u16>,
+}
+
+impl PayoutTable {
+    /// The game's current payouts
+    pub const STANDARD: PayoutTable = PayoutTable {
+        payouts: [
+            10000, 36, 720, 360, 80, 252, 108, 72, 54, 180, 72, 180, 119, 36, 306, 1080, 144, 1800,
+            3600,
+        ],
+        bonus: 0,
+    };
+
+    /// Create a table from the payouts of every sum from [`MIN_SUM`] to [`MAX_SUM`]
+    #[inline]
+    pub fn new(payouts: [u16; NUM_SUMS]) -> Self {
+        Self { payouts, bonus: 0 }
+    }
+
+    /// Same as [`new`](Self::new), but checks the number of payouts at runtime
+    pub fn from_slice(payouts: &[u16]) -> Result<Self, PayoutTableError> {
+        if payouts.len() != NUM_SUMS {
+            return Err(PayoutTableError::InvalidLength(payouts.len()));
+        }
+
+        let mut out = [0u16; NUM_SUMS];
+        out.copy_from_slice(payouts);
+
+        Ok(Self::new(out))
+    }
+
+    /// Parse a TOML document with a `payouts` array containing the payouts of every sum
+    /// from 6 to 24, e.g.
+    ///
+    /// ```toml
+    /// payouts = [
+    ///     10000, 36, 720, 360, 80, 252, 108, 72, 54, 180,
+    ///     72, 180, 119, 36, 306, 1080, 144, 1800, 3600,
+    /// ]
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn from_toml(src: &str) -> Result<Self, PayoutTableError> {
+        let file: PayoutTableFile = toml::from_str(src).map_err(PayoutTableError::Parse)?;
+
+        Self::from_slice(&file.payouts)
+    }
+
+    /// Read a table in the [`from_toml`](Self::from_toml) format from a file
+    #[cfg(feature = "toml")]
+    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> Result<Self, PayoutTableError> {
+        let src = std::fs::read_to_string(path).map_err(PayoutTableError::Io)?;
+
+        Self::from_toml(&src)
+    }
+
+    /// Parse a JSON document with a `payouts` array containing the payouts of every sum from 6
+    /// to 24, e.g.
+    ///
+    /// ```json
+    /// {"payouts": [10000, 36, 720, 360, 80, 252, 108, 72, 54, 180, 72, 180, 119, 36, 306, 1080,
+    ///     144, 1800, 3600]}
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn from_json(src: &str) -> Result<Self, PayoutTableError> {
+        let file: PayoutTableFile =
+            serde_json::from_str(src).map_err(PayoutTableError::ParseJson)?;
+
+        Self::from_slice(&file.payouts)
+    }
+
+    /// Read a table in the [`from_json`](Self::from_json) format from a file
+    #[cfg(feature = "json")]
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self, PayoutTableError> {
+        let src = std::fs::read_to_string(path).map_err(PayoutTableError::Io)?;
+
+        Self::from_json(&src)
+    }
+
+    /// The same table with an MGP bonus of the given percentage applied, e.g. 15 for +15%.
+    /// Replaces any previously applied bonus.
+    #[inline]
+    pub fn with_bonus(self, percent: u16) -> Self {
+        Self {
+            bonus: percent,
+            ..self
+        }
+    }
+
+    /// Active MGP bonus percentage
+    #[inline]
+    pub fn bonus(&self) -> u16 {
+        self.bonus
+    }
+
+    /// Payout for the given line sum before any bonus; 0 for sums no line can add up to
+    #[inline]
+    pub fn base_payout(&self, sum: u8) -> u16 {
+        match (MIN_SUM..=MAX_SUM).contains(&sum) {
+            true => self.payouts[(sum - MIN_SUM) as usize],
+            false => 0,
+        }
+    }
+
+    /// Highest payout on the table, bonus included
+    pub fn max_payout(&self) -> u16 {
+        (MIN_SUM..=MAX_SUM)
+            .map(|sum| self.payout(sum))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// MGP received for the given line sum, bonus included. Like the game, fractions of an MGP
+    /// are dropped.
+    #[inline]
+    pub fn payout(&self, sum: u8) -> u16 {
+        let base = self.base_payout(sum) as u32;
+        let boosted = base * (100 + self.bonus as u32) / 100;
+
+        boosted.min(u16::MAX as u32) as u16
+    }
+}
+
+impl Default for PayoutTable {
+    #[inline]
+    fn default() -> Self {
+        Self::STANDARD
     }
 }
 
@@ -16,6 +197,11 @@ pub fn payout_for_points(points: u8) -> u16 {
 mod test {
     use super::*;
 
+    const PAYOUTS_ARRAY: [u16; 25] = [
+        0u16, 0, 0, 0, 0, 0, 10000, 36, 720, 360, 80, 252, 108, 72, 54, 180, 72, 180, 119, 36, 306,
+        1080, 144, 1800, 3600,
+    ];
+
     #[test]
     fn std_values() {
         for i in 0u8..25 {
@@ -31,4 +217,85 @@ mod test {
             assert_eq!(0, payout_for_points(i), "For {} points", i);
         }
     }
+
+    #[test]
+    fn default_table() {
+        assert_eq!(PayoutTable::default(), PayoutTable::STANDARD);
+    }
+
+    #[test]
+    fn from_slice() {
+        let mut payouts = [1u16; NUM_SUMS];
+        payouts[0] = 5;
+        let table = PayoutTable::from_slice(&payouts).unwrap();
+
+        assert_eq!(table.payout(6), 5);
+        assert_eq!(table.payout(7), 1);
+        assert_eq!(table.payout(24), 1);
+        assert_eq!(table.payout(25), 0);
+
+        match PayoutTable::from_slice(&payouts[1..]) {
+            Err(PayoutTableError::InvalidLength(18)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn bonus() {
+        let table = PayoutTable::STANDARD.with_bonus(15);
+
+        assert_eq!(table.bonus(), 15);
+        assert_eq!(table.payout(6), 11500);
+        assert_eq!(table.base_payout(6), 10000);
+        // 119 * 1.15 = 136.85
+        assert_eq!(table.payout(18), 136);
+        assert_eq!(table.payout(25), 0);
+        assert_eq!(table.max_payout(), 11500);
+        assert_eq!(table.with_bonus(0), PayoutTable::STANDARD);
+    }
+
+    #[test]
+    fn bonus_saturates() {
+        assert_eq!(
+            PayoutTable::STANDARD.with_bonus(u16::MAX).payout(6),
+            u16::MAX
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn from_toml() {
+        let src = "payouts = [10000, 36, 720, 360, 80, 252, 108, 72, 54, 180, 72, 180, 119, 36, 306, 1080, 144, 1800, 3600]";
+
+        assert_eq!(PayoutTable::from_toml(src).unwrap(), PayoutTable::STANDARD);
+        assert!(matches!(
+            PayoutTable::from_toml("payouts = [1, 2]"),
+            Err(PayoutTableError::InvalidLength(2))
+        ));
+        assert!(matches!(
+            PayoutTable::from_toml("payouts = 1"),
+            Err(PayoutTableError::Parse(_))
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn from_json() {
+        let src = r#"{"payouts": [10000, 36, 720, 360, 80, 252, 108, 72, 54, 180, 72, 180, 119, 36,
+            306, 1080, 144, 1800, 3600]}"#;
+
+        assert_eq!(PayoutTable::from_json(src).unwrap(), PayoutTable::STANDARD);
+        assert!(matches!(
+            PayoutTable::from_json(r#"{"payouts": [1, 2]}"#),
+            Err(PayoutTableError::InvalidLength(2))
+        ));
+        assert!(matches!(
+            PayoutTable::from_json(r#"{"payouts": 1}"#),
+            Err(PayoutTableError::ParseJson(_))
+        ));
+        assert!(matches!(
+            PayoutTable::from_json_file("no/such/file.json"),
+            Err(PayoutTableError::Io(_))
+        ));
+    }
 }
@@ -0,0 +1,213 @@
+//! Optimal reveal values for every state with 1 to 3 reveals, under the
+//! [standard payouts](crate::PayoutTable::STANDARD), going for the highest expected value.
+//!
+//! The table's generated by `examples/generate_policy_table.rs`. Only [canonical
+//! boards](Board::canonical) are stored: every other board gets looked up by its canonical board
+//! & the answer mapped back through the [`Transform`](crate::Transform) between the two.
+//!
+//! Layout, all fixed width integers being little endian:
+//!
+//! 1. `u32` number of states
+//! 2. for each state, ordered by key: the `u32` key, then the `u32` offset of its totals into the
+//!    data section. A state's key is its cells read as a base 10 number, top left first, with 0
+//!    for empty cells.
+//! 3. the data section: for each state, the payout total of revealing each of its empty cells in
+//!    cell index order, as LEB128 varints
+
+use super::solver::{Solution, MAX_REVEALS};
+use super::Board;
+
+const TABLE: &[u8] = include_bytes!("../data/policy_table.bin");
+
+const NUM_CELLS: usize = 9;
+
+/// Size of a state's entry in the index
+const ENTRY_LEN: usize = 8;
+
+#[inline]
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    let mut out = [0u8; 4];
+    out.copy_from_slice(bytes.get(at..at + 4)?);
+
+    Some(u32::from_le_bytes(out))
+}
+
+/// Read the LEB128 varint at the given offset, moving the offset past it
+fn read_varint(bytes: &[u8], at: &mut usize) -> Option<u64> {
+    let mut out = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*at)?;
+        *at += 1;
+        out |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(out);
+        }
+    }
+
+    None
+}
+
+/// Key of the state with the given cells
+#[inline]
+fn state_key(cells: &[u8; NUM_CELLS]) -> u32 {
+    cells.iter().fold(0, |key, v| key * 10 + *v as u32)
+}
+
+/// Index & data sections of the table
+fn sections() -> Option<(usize, &'static [u8], &'static [u8])> {
+    let num_states = read_u32(TABLE, 0)? as usize;
+    let index_end = 4 + num_states * ENTRY_LEN;
+
+    Some((
+        num_states,
+        TABLE.get(4..index_end)?,
+        TABLE.get(index_end..)?,
+    ))
+}
+
+/// Offset into the data section of the state with the given key
+fn find_state(key: u32) -> Option<usize> {
+    let (num_states, index, _) = sections()?;
+    let (mut lo, mut hi) = (0, num_states);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let at = mid * ENTRY_LEN;
+        match read_u32(index, at)?.cmp(&key) {
+            std::cmp::Ordering::Less => lo = mid + 1,
+            std::cmp::Ordering::Greater => hi = mid,
+            std::cmp::Ordering::Equal => return read_u32(index, at + 4).map(|v| v as usize),
+        }
+    }
+
+    None
+}
+
+/// Reveal values of the board. `None` for boards outside the table.
+pub fn lookup(board: &Board) -> Option<Solution> {
+    let num_fills = board.len();
+    if num_fills == 0 || num_fills >= MAX_REVEALS {
+        return None;
+    }
+
+    let (canonical, transform) = board.canonical();
+    let cells = canonical.cells();
+    let mut at = find_state(state_key(&cells))?;
+    let (_, _, data) = sections()?;
+
+    let mut totals = [None; NUM_CELLS];
+    for (value, total) in cells.iter().zip(totals.iter_mut()) {
+        if *value == 0 {
+            *total = Some(read_varint(data, &mut at)?);
+        }
+    }
+
+    let num_endings = (1..=(NUM_CELLS as u64 - num_fills as u64)).product();
+
+    Some(Solution::from_payout_totals(
+        transform.cells_from_transformed(&totals),
+        num_endings,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::solver::solve;
+    use crate::{BoardPosition, Objective, PayoutTable, ValuedBoardPosition};
+
+    fn assert_matches_live(board: &Board) {
+        let live = solve(board, &PayoutTable::STANDARD, Objective::ExpectedValue);
+        let table = lookup(board).unwrap();
+
+        for idx in 0..NUM_CELLS as u8 {
+            let pos = BoardPosition::from_index(idx);
+            assert_eq!(
+                table.expected_value(pos),
+                live.expected_value(pos),
+                "{} {}",
+                board,
+                pos
+            );
+            assert_eq!(table.score(pos), live.score(pos), "{} {}", board, pos);
+        }
+        assert_eq!(table.best_cells(), live.best_cells(), "{}", board);
+    }
+
+    /// A random board with the given number of fills
+    fn random_board(rng: &mut StdRng, num_fills: usize) -> Board {
+        let mut cells: Vec<u8> = (0..NUM_CELLS as u8).collect();
+        let mut values: Vec<u8> = (1..=NUM_CELLS as u8).collect();
+        cells.shuffle(rng);
+        values.shuffle(rng);
+
+        let mut board = Board::default();
+        for (idx, value) in cells.iter().zip(values.iter()).take(num_fills) {
+            board
+                .fill(ValuedBoardPosition::from_pos(
+                    *value,
+                    BoardPosition::from_index(*idx),
+                ))
+                .unwrap();
+        }
+
+        board
+    }
+
+    #[test]
+    fn layout() {
+        let (num_states, index, data) = sections().unwrap();
+        let mut prev_key = None;
+        let mut end = 0;
+        for i in 0..num_states {
+            let key = read_u32(index, i * ENTRY_LEN).unwrap();
+            let offset = read_u32(index, i * ENTRY_LEN + 4).unwrap() as usize;
+            assert!(prev_key < Some(key), "{}", key);
+            assert_eq!(offset, end, "{}", key);
+
+            let num_empty = (0..NUM_CELLS as u32)
+                .map(|i| key / 10u32.pow(i) % 10)
+                .filter(|digit| *digit == 0)
+                .count();
+            for _ in 0..num_empty {
+                read_varint(data, &mut end).unwrap();
+            }
+            prev_key = Some(key);
+        }
+
+        assert_eq!(end, data.len());
+    }
+
+    #[test]
+    fn matches_live_solver() {
+        // Every board with 1 fill
+        for idx in 0..NUM_CELLS as u8 {
+            for value in 1..=NUM_CELLS as u8 {
+                let mut board = Board::default();
+                board
+                    .fill(ValuedBoardPosition::from_pos(
+                        value,
+                        BoardPosition::from_index(idx),
+                    ))
+                    .unwrap();
+                assert_matches_live(&board);
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(12);
+        for (num_fills, num_samples) in [(2, 100), (3, 300)].iter() {
+            for _ in 0..*num_samples {
+                assert_matches_live(&random_board(&mut rng, *num_fills));
+            }
+        }
+    }
+
+    #[test]
+    fn outside_the_table() {
+        assert!(lookup(&Board::default()).is_none());
+        assert!(lookup(&"123/4__/___".parse().unwrap()).is_none());
+    }
+}
@@ -1,6 +1,31 @@
 pub use super::board::available_selection_iter::AvailableSelectionIter;
+pub use super::board::edit::Edit;
+pub use super::board::ending_iter::EndingIter;
 pub use super::board::fill_failure::FillFailure;
+pub use super::board::notation::ParseBoardError;
+pub use super::board::transform::Transform;
 pub use super::board::Board;
+pub use super::board_position::position_error::PositionError;
 pub use super::board_position::valued_board_position::ValuedBoardPosition;
 pub use super::board_position::BoardPosition;
+pub use super::ending::Ending;
+pub use super::expected_value::ExpectedValue;
+pub use super::game::{Game, GameError, Phase};
+pub use super::game_value::GameValue;
+pub use super::line::distribution::LineDistribution;
+pub use super::line::line_map::LineMap;
+pub use super::line::summary::LineSummary;
+pub use super::line::Line;
+pub use super::objective::{Objective, VARIANCE_CEILING};
+pub use super::payouts::{PayoutTable, PayoutTableError};
+pub use super::recommendation::reveal_preview::{RevealOutcome, RevealPreview};
 pub use super::recommendation::Recommendation;
+pub use super::session::Session;
+#[cfg(feature = "parallel")]
+pub use super::simulation::par_evaluate;
+pub use super::simulation::{
+    evaluate, HiddenBoard, PolicyEvaluation, SimulationError, SimulationReport, Simulator,
+};
+pub use super::strategy::{
+    GreedyStrategy, HeuristicStrategy, OptimalStrategy, RandomStrategy, Strategy,
+};
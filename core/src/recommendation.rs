@@ -1,22 +1,112 @@
-use super::end_board::EndBoardGenerator;
-use super::end_row::DiagRow;
-use super::{Board, BoardPosition};
+use super::line::stats::LineStats;
+use super::line::{best_lines, NUM_LINES};
+use super::packed_board::PackedBoard;
+use super::solver::{self, MAX_REVEALS};
+use super::{
+    Board, BoardPosition, ExpectedValue, Line, LineDistribution, LineMap, LineSummary, Objective,
+    PayoutTable,
+};
 
-#[derive(Debug)]
+pub mod reveal_preview;
+
+/// Suggested cells to reveal & lines to pick for a board.
+///
+/// Serialized as a struct with the fields
+/// - `max_avg`: highest expected payout of any line
+/// - `suggestions` & `heuristic_suggestions`: cells to reveal next by the solver & by the
+///   heuristic
+/// - `reveal_values` & `reveal_scores`: expected payout & [objective](Objective) score of
+///   revealing each cell, by [position index](BoardPosition::index); `null` for revealed cells
+/// - `lines`: the [`LineSummary`] of each line, in a [`LineMap`]
+/// - `objective`
+/// - `best_lines`: lines to pick, never empty
+///
+/// Suggested cells must have a reveal value & score.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(into = "RecommendationRepr", try_from = "RecommendationRepr")
+)]
 pub struct Recommendation {
-    avg_col: [u16; 3],
-    avg_row: [u16; 3],
-    avg_tl_br: u16,
-    avg_bl_tr: u16,
-    max_avg: u16,
+    max_avg: ExpectedValue,
+    suggestions: Vec<BoardPosition>,
+    heuristic_suggestions: Vec<BoardPosition>,
+    reveal_values: [Option<ExpectedValue>; 9],
+    reveal_scores: [Option<ExpectedValue>; 9],
+    lines: LineMap<LineSummary>,
+    objective: Objective,
+    best_lines: Vec<Line>,
+}
+
+/// Serialized form of a [`Recommendation`]; see its docs for the fields
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RecommendationRepr {
+    max_avg: ExpectedValue,
     suggestions: Vec<BoardPosition>,
+    heuristic_suggestions: Vec<BoardPosition>,
+    reveal_values: [Option<ExpectedValue>; 9],
+    reveal_scores: [Option<ExpectedValue>; 9],
+    lines: LineMap<LineSummary>,
+    objective: Objective,
+    best_lines: Vec<Line>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Recommendation> for RecommendationRepr {
+    fn from(rec: Recommendation) -> Self {
+        Self {
+            max_avg: rec.max_avg,
+            suggestions: rec.suggestions,
+            heuristic_suggestions: rec.heuristic_suggestions,
+            reveal_values: rec.reveal_values,
+            reveal_scores: rec.reveal_scores,
+            lines: rec.lines,
+            objective: rec.objective,
+            best_lines: rec.best_lines,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<RecommendationRepr> for Recommendation {
+    type Error = &'static str;
+
+    fn try_from(repr: RecommendationRepr) -> Result<Self, Self::Error> {
+        if repr.best_lines.is_empty() {
+            return Err("A recommendation needs a line to pick");
+        }
+        let revealable = |pos: &BoardPosition| {
+            let idx = pos.index() as usize;
+            repr.reveal_values[idx].is_some() && repr.reveal_scores[idx].is_some()
+        };
+        let mut suggested = repr.suggestions.iter().chain(&repr.heuristic_suggestions);
+        if !suggested.all(revealable) {
+            return Err("Suggested cells need a reveal value & score");
+        }
+
+        Ok(Self {
+            max_avg: repr.max_avg,
+            suggestions: repr.suggestions,
+            heuristic_suggestions: repr.heuristic_suggestions,
+            reveal_values: repr.reveal_values,
+            reveal_scores: repr.reveal_scores,
+            lines: repr.lines,
+            objective: repr.objective,
+            best_lines: repr.best_lines,
+        })
+    }
 }
 
-fn validate_board(b: &Board) -> Result<(), &'static str> {
+/// Why there's nothing to recommend for an empty board
+pub(crate) const NO_REVEALS_YET: &str = "The first position is chosen for you";
+
+pub(crate) fn validate_board(b: &Board) -> Result<(), &'static str> {
     let f = b.len();
     if f == 0 {
-        return Err("The first position is chosen for you");
-    } else if f > 4 {
+        return Err(NO_REVEALS_YET);
+    } else if f > MAX_REVEALS {
         return Err("Time to pick a row");
     }
 
@@ -24,51 +114,46 @@ fn validate_board(b: &Board) -> Result<(), &'static str> {
 }
 
 impl Recommendation {
-    fn cmp_num(num: u16, max: u16) -> u8 {
+    fn cmp_num(num: ExpectedValue, max: ExpectedValue) -> u8 {
         match num == max {
             true => 1,
             false => 0,
         }
     }
 
-    fn calc_max_avg(averages: &[u16; 8], previous_attempt_average: u16) -> Option<u16> {
+    pub(crate) fn calc_max_avg(
+        averages: &[ExpectedValue; NUM_LINES],
+        previous_attempt_average: Option<ExpectedValue>,
+    ) -> Option<ExpectedValue> {
         let max_avg = averages.iter();
-        let max_avg = match previous_attempt_average == u16::MAX {
-            true => max_avg.max(),
-            false => max_avg.filter(|v| v < &&previous_attempt_average).max(),
+        let max_avg = match previous_attempt_average {
+            None => max_avg.max(),
+            Some(prev) => max_avg.filter(|v| **v < prev).max(),
         };
 
-        max_avg.map(|v| *v)
+        max_avg.copied()
     }
 
-    fn mk_suggestions(board: &Board, averages: &[u16; 8], curr_max_avg: u16) -> Vec<BoardPosition> {
+    pub(crate) fn mk_suggestions(
+        board: &Board,
+        averages: &[ExpectedValue; NUM_LINES],
+        curr_max_avg: ExpectedValue,
+    ) -> Vec<BoardPosition> {
         let mut suggestions = Vec::with_capacity(1);
         let mut curr_max_matches = 0u8;
 
         for col in 0..3 {
-            // 1 = true, 0 = false
-            let matches_col = Self::cmp_num(averages[col], curr_max_avg);
-
             for row in 0..3 {
-                let pos = BoardPosition::new(col as u8, row as u8);
+                let pos = BoardPosition::new(col, row);
                 if board.contains_position(pos) {
                     continue;
                 }
 
-                // 1 = true, 0 = false
-                let matches_row = Self::cmp_num(averages[row + 3], curr_max_avg);
+                let total_plus: u8 = pos
+                    .lines()
+                    .map(|line| Self::cmp_num(averages[line.index() as usize], curr_max_avg))
+                    .sum();
 
-                let diag_plus = match pos.diag_row() {
-                    DiagRow::Both => {
-                        Self::cmp_num(averages[6], curr_max_avg)
-                            + Self::cmp_num(averages[7], curr_max_avg)
-                    }
-                    DiagRow::None => 0,
-                    DiagRow::BottomLeftTopRight => Self::cmp_num(averages[7], curr_max_avg),
-                    DiagRow::TopLeftBottomRight => Self::cmp_num(averages[6], curr_max_avg),
-                };
-
-                let total_plus = matches_col + matches_row + diag_plus;
                 if total_plus == curr_max_matches {
                     suggestions.push(pos);
                 } else if total_plus > curr_max_matches {
@@ -80,8 +165,8 @@ impl Recommendation {
         }
 
         if suggestions.is_empty() {
-            if let Some(next) = Self::calc_max_avg(&averages, curr_max_avg) {
-                return Self::mk_suggestions(&board, &averages, next);
+            if let Some(next) = Self::calc_max_avg(averages, Some(curr_max_avg)) {
+                return Self::mk_suggestions(board, averages, next);
             }
         }
 
@@ -89,68 +174,484 @@ impl Recommendation {
         suggestions
     }
 
+    #[deprecated(note = "use `line_value(Line::col(..))` or `lines()` instead")]
     #[inline]
-    pub fn avg_col(&self) -> &[u16; 3] {
-        &self.avg_col
+    pub fn avg_col(&self) -> [ExpectedValue; 3] {
+        [
+            self.lines[Line::Col0].value(),
+            self.lines[Line::Col1].value(),
+            self.lines[Line::Col2].value(),
+        ]
     }
 
+    #[deprecated(note = "use `line_value(Line::row(..))` or `lines()` instead")]
     #[inline]
-    pub fn avg_row(&self) -> &[u16; 3] {
-        &self.avg_row
+    pub fn avg_row(&self) -> [ExpectedValue; 3] {
+        [
+            self.lines[Line::Row0].value(),
+            self.lines[Line::Row1].value(),
+            self.lines[Line::Row2].value(),
+        ]
     }
 
+    #[deprecated(note = "use `line_value(Line::TopLeftBottomRight)` instead")]
     #[inline]
-    pub fn avg_tl_br(&self) -> u16 {
-        self.avg_tl_br
+    pub fn avg_tl_br(&self) -> ExpectedValue {
+        self.lines[Line::TopLeftBottomRight].value()
     }
 
+    #[deprecated(note = "use `line_value(Line::BottomLeftTopRight)` instead")]
     #[inline]
-    pub fn avg_bl_tr(&self) -> u16 {
-        self.avg_bl_tr
+    pub fn avg_bl_tr(&self) -> ExpectedValue {
+        self.lines[Line::BottomLeftTopRight].value()
     }
 
     #[inline]
-    pub fn max_avg(&self) -> u16 {
+    pub fn max_avg(&self) -> ExpectedValue {
         self.max_avg
     }
 
+    /// What the [suggestions](Self::suggestions) & [best lines](Self::best_lines) are optimised for
+    #[inline]
+    pub fn objective(&self) -> Objective {
+        self.objective
+    }
+
+    /// Cells with the best [objective](Self::objective) score, assuming optimal play afterwards.
+    /// Ties are broken by the expected payout.
     #[inline]
     pub fn suggestions(&self) -> &Vec<BoardPosition> {
         &self.suggestions
     }
 
+    /// Expected final payout of scratching the given cell, assuming optimal play afterwards.
+    /// `None` if the cell's already been revealed, isn't on the board or there are no scratches
+    /// left.
+    #[inline]
+    pub fn reveal_value(&self, pos: BoardPosition) -> Option<ExpectedValue> {
+        self.reveal_values
+            .get(pos.index() as usize)
+            .copied()
+            .flatten()
+    }
+
+    /// [Objective](Self::objective) score of scratching the given cell, assuming optimal play
+    /// afterwards: the expected payout, the chance of winning the targeted amount or
+    /// [`VARIANCE_CEILING`](crate::VARIANCE_CEILING) minus the expected variance.
+    /// `None` if the cell's already been revealed, isn't on the board or there are no scratches
+    /// left.
+    #[inline]
+    pub fn reveal_score(&self, pos: BoardPosition) -> Option<ExpectedValue> {
+        self.reveal_scores
+            .get(pos.index() as usize)
+            .copied()
+            .flatten()
+    }
+
+    /// Unrevealed cells along with their [reveal value](Self::reveal_value), best
+    /// [scoring](Self::reveal_score) first
+    pub fn ranked_reveals(&self) -> Vec<(BoardPosition, ExpectedValue)> {
+        let mut out: Vec<(BoardPosition, ExpectedValue, ExpectedValue)> = self
+            .reveal_values
+            .iter()
+            .zip(self.reveal_scores.iter())
+            .enumerate()
+            .filter_map(|(idx, (v, s))| match (v, s) {
+                (Some(v), Some(s)) => Some((BoardPosition::from_index(idx as u8), *v, *s)),
+                _ => None,
+            })
+            .collect();
+        out.sort_by(|(_, v1, s1), (_, v2, s2)| (s2, v2).cmp(&(s1, v1)));
+
+        out.into_iter().map(|(pos, v, _)| (pos, v)).collect()
+    }
+
+    /// Where each line stands on the current board
+    #[inline]
+    pub fn lines(&self) -> &LineMap<LineSummary> {
+        &self.lines
+    }
+
+    /// Expected payout of picking the given line on the current board
+    #[inline]
+    pub fn line_value(&self, line: Line) -> ExpectedValue {
+        self.lines[line].value()
+    }
+
+    /// [Objective](Self::objective) score of picking the given line on the current board
+    #[inline]
+    pub fn line_score(&self, line: Line) -> ExpectedValue {
+        self.lines[line].score()
+    }
+
+    /// Lines sharing the best [objective](Self::objective) score on the current board, ties broken
+    /// by the expected payout. This is what to pick once all the scratches have been used up.
+    #[inline]
+    pub fn best_lines(&self) -> &Vec<Line> {
+        &self.best_lines
+    }
+
+    /// Payout distribution of the given line across the board's possible endings
+    #[inline]
+    pub fn distribution(&self, line: Line) -> &LineDistribution {
+        self.lines[line].distribution()
+    }
+
+    /// Cells touching the most lines tied for the highest average
+    #[inline]
+    pub fn heuristic_suggestions(&self) -> &Vec<BoardPosition> {
+        &self.heuristic_suggestions
+    }
+
+    /// Recommendation for the [standard payouts](PayoutTable::STANDARD)
+    #[inline]
     pub fn from_board(board: &Board) -> Result<Recommendation, &'static str> {
-        validate_board(&board)?;
+        Self::from_board_with(board, &PayoutTable::STANDARD)
+    }
 
-        let eb = board.available_endings();
+    /// Recommendation for the highest expected payout under the given payouts
+    #[inline]
+    pub fn from_board_with(
+        board: &Board,
+        payouts: &PayoutTable,
+    ) -> Result<Recommendation, &'static str> {
+        Self::from_board_for(board, payouts, Objective::ExpectedValue)
+    }
 
-        let c0 = eb.avg_for_col(0);
-        let c1 = eb.avg_for_col(1);
-        let c2 = eb.avg_for_col(2);
+    /// Recommendation optimised for the given objective. The heuristic suggestions always go for
+    /// the highest expected payout.
+    pub fn from_board_for(
+        board: &Board,
+        payouts: &PayoutTable,
+        objective: Objective,
+    ) -> Result<Recommendation, &'static str> {
+        validate_board(board)?;
 
-        let r0 = eb.avg_for_row(0);
-        let r1 = eb.avg_for_row(1);
-        let r2 = eb.avg_for_row(2);
+        let stats = PackedBoard::from_board(board).line_stats();
 
-        let avg_tl_br = eb.avg_for_diag_row(DiagRow::TopLeftBottomRight);
-        let avg_bl_tr = eb.avg_for_diag_row(DiagRow::BottomLeftTopRight);
+        Ok(Self::from_stats(board, &stats, payouts, objective))
+    }
 
-        let avg_col = [c0, c1, c2];
-        let avg_row = [r0, r1, r2];
-        let averages = [c0, c1, c2, r0, r1, r2, avg_tl_br, avg_bl_tr];
+    /// Recommendation from line stats already collected across the board's endings
+    pub(crate) fn from_stats(
+        board: &Board,
+        stats: &LineStats,
+        payouts: &PayoutTable,
+        objective: Objective,
+    ) -> Recommendation {
+        let averages = stats.averages(payouts);
 
-        let max_avg = Self::calc_max_avg(&averages, u16::MAX).unwrap();
-        let suggestions = Self::mk_suggestions(&board, &averages, max_avg);
+        let max_avg = Self::calc_max_avg(&averages, None).unwrap();
+        let heuristic_suggestions = Self::mk_suggestions(board, &averages, max_avg);
+        let solution = solver::lookup_or_solve(board, payouts, objective);
+        let suggestions = solution.best_cells();
 
-        let out = Recommendation {
-            avg_col,
-            avg_row,
-            avg_tl_br,
-            avg_bl_tr,
+        let mut reveal_values = [None; 9];
+        let mut reveal_scores = [None; 9];
+        for idx in 0..9 {
+            let pos = BoardPosition::from_index(idx as u8);
+            reveal_values[idx] = solution.expected_value(pos);
+            reveal_scores[idx] = solution.score(pos);
+        }
+
+        let line_scores = stats.scores(payouts, &objective.scorer(payouts));
+        let best_lines = best_lines(&line_scores, &averages);
+
+        let mut lines = LineMap::default();
+        for (line, distribution) in Line::iter().zip(stats.distributions(payouts).iter()) {
+            let idx = line.index() as usize;
+            lines[line] = LineSummary::new(averages[idx], line_scores[idx], distribution.clone());
+        }
+
+        Recommendation {
             max_avg,
             suggestions,
-        };
+            heuristic_suggestions,
+            reveal_values,
+            reveal_scores,
+            lines,
+            objective,
+            best_lines,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::payouts::{payout_for_points, NUM_SUMS};
+    use crate::VARIANCE_CEILING;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let board = "1__/_5_/__9".parse::<Board>().unwrap();
+        let rec = Recommendation::from_board_for(
+            &board,
+            &PayoutTable::STANDARD,
+            Objective::AtLeast(1080),
+        )
+        .unwrap();
+        let json = serde_json::to_string(&rec).unwrap();
+        let back: Recommendation = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+        assert_eq!(back.suggestions(), rec.suggestions());
+        assert_eq!(back.objective(), rec.objective());
+        for line in Line::ALL.iter() {
+            assert_eq!(back.lines()[*line], rec.lines()[*line], "{}", line);
+        }
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["best_lines"] = serde_json::json!([]);
+        assert!(serde_json::from_value::<Recommendation>(value).is_err());
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let idx = rec.suggestions()[0].index() as usize;
+        value["reveal_values"][idx] = serde_json::Value::Null;
+        assert!(serde_json::from_value::<Recommendation>(value).is_err());
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["max_avg"]["count"] = serde_json::json!(0);
+        assert!(serde_json::from_value::<Recommendation>(value).is_err());
+    }
+
+    #[test]
+    fn lines_once_full() {
+        let board = "1_8/_2_/9__".parse::<Board>().unwrap();
+        let rec = Recommendation::from_board(&board).unwrap();
+
+        let mut exp = [0u64; NUM_LINES];
+        PackedBoard::from_board(&board).for_each_ending(|cells| {
+            for (total, line) in exp.iter_mut().zip(Line::ALL.iter()) {
+                let sum = line
+                    .positions()
+                    .iter()
+                    .map(|p| cells[p.index() as usize])
+                    .sum();
+                *total += payout_for_points(sum) as u64;
+            }
+        });
+        let max = *exp.iter().max().unwrap();
+
+        assert!(rec.suggestions().is_empty());
+        for line in Line::ALL.iter() {
+            let total = exp[line.index() as usize];
+
+            assert_eq!(
+                rec.line_value(*line),
+                ExpectedValue::new(total, 120),
+                "{}",
+                line
+            );
+            assert_eq!(
+                rec.distribution(*line).mean(),
+                rec.line_value(*line).as_f64(),
+                "{}",
+                line
+            );
+            assert_eq!(rec.best_lines().contains(line), total == max, "{}", line);
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn line_averages() {
+        let board = "1_8/_2_/9__".parse::<Board>().unwrap();
+        let rec = Recommendation::from_board(&board).unwrap();
+
+        for i in 0..3 {
+            assert_eq!(rec.avg_col()[i as usize], rec.line_value(Line::col(i)));
+            assert_eq!(rec.avg_row()[i as usize], rec.line_value(Line::row(i)));
+        }
+        assert_eq!(rec.avg_tl_br(), rec.line_value(Line::TopLeftBottomRight));
+        assert_eq!(rec.avg_bl_tr(), rec.line_value(Line::BottomLeftTopRight));
+    }
+
+    #[test]
+    fn best_line_ties() {
+        // Mirrored left to right, the board stays the same
+        let board = "_1_/_5_/_9_".parse::<Board>().unwrap();
+        let rec = Recommendation::from_board(&board).unwrap();
+        let best = rec.best_lines();
+
+        assert_eq!(
+            rec.line_value(Line::TopLeftBottomRight),
+            rec.line_value(Line::BottomLeftTopRight)
+        );
+        assert_eq!(rec.line_value(Line::Col0), rec.line_value(Line::Col2));
+        assert_eq!(
+            best.contains(&Line::TopLeftBottomRight),
+            best.contains(&Line::BottomLeftTopRight)
+        );
+        assert_eq!(best.contains(&Line::Col0), best.contains(&Line::Col2));
+    }
+
+    #[test]
+    fn custom_payouts() {
+        // Only the middle row can still add up to 6
+        let board = "987/___/6__".parse::<Board>().unwrap();
+        let mut payouts = [0; NUM_SUMS];
+        payouts[0] = 100;
+        let payouts = PayoutTable::new(payouts);
+        let rec = Recommendation::from_board_with(&board, &payouts).unwrap();
+
+        assert_eq!(rec.best_lines(), &vec![Line::Row1]);
+        assert_eq!(rec.line_value(Line::Row1), ExpectedValue::new(100 * 6, 60));
+        assert_eq!(rec.line_value(Line::Row0), ExpectedValue::new(0, 1));
+    }
+
+    #[test]
+    fn bonus() {
+        let board = "1_8/_2_/9__".parse::<Board>().unwrap();
+        let base = Recommendation::from_board(&board).unwrap();
+        let payouts = PayoutTable::STANDARD.with_bonus(10);
+        let boosted = Recommendation::from_board_with(&board, &payouts).unwrap();
+
+        for line in Line::ALL.iter() {
+            assert!(
+                boosted.line_value(*line) >= base.line_value(*line),
+                "{}",
+                line
+            );
+        }
+        // 1 + 2 + 3 is still possible on the diagonal
+        assert_eq!(boosted.distribution(Line::TopLeftBottomRight).max(), 11000);
+    }
+
+    #[test]
+    fn ranked_reveals() {
+        let board = "_8_/7__/__3".parse::<Board>().unwrap();
+        let rec = Recommendation::from_board(&board).unwrap();
+        let ranked = rec.ranked_reveals();
+
+        assert_eq!(ranked.len(), 6);
+        assert!(rec.suggestions().contains(&ranked[0].0));
+        for pair in ranked.windows(2) {
+            assert!(pair[0].1 >= pair[1].1);
+        }
+        for (pos, value) in ranked.iter() {
+            assert_eq!(rec.reveal_value(*pos), Some(*value), "{}", pos);
+        }
+        assert_eq!(rec.reveal_value(BoardPosition::never()), None);
+        assert_eq!(rec.reveal_score(BoardPosition::never()), None);
+    }
+
+    #[test]
+    fn objective_defaults_to_ev() {
+        let board = "_8_/7__/__3".parse::<Board>().unwrap();
+        let rec = Recommendation::from_board(&board).unwrap();
+
+        assert_eq!(rec.objective(), Objective::ExpectedValue);
+        for line in Line::ALL.iter() {
+            assert_eq!(rec.line_score(*line), rec.line_value(*line), "{}", line);
+        }
+        for (pos, value) in rec.ranked_reveals() {
+            assert_eq!(rec.reveal_score(pos), Some(value), "{}", pos);
+        }
+    }
+
+    #[test]
+    fn jackpot_lines() {
+        // Only the top left to bottom right diagonal can still add up to 6
+        let board = "19_/82_/___".parse::<Board>().unwrap();
+        let rec =
+            Recommendation::from_board_for(&board, &PayoutTable::STANDARD, Objective::Jackpot)
+                .unwrap();
+
+        assert_eq!(rec.best_lines(), &vec![Line::TopLeftBottomRight]);
+        assert_eq!(
+            rec.line_score(Line::TopLeftBottomRight),
+            ExpectedValue::new(1, 5)
+        );
+        assert_eq!(rec.line_score(Line::Row0), ExpectedValue::new(0, 1));
+    }
+
+    #[test]
+    fn at_least_scores() {
+        let board = "1_8/_2_/9__".parse::<Board>().unwrap();
+        let objective = Objective::AtLeast(720);
+        let rec =
+            Recommendation::from_board_for(&board, &PayoutTable::STANDARD, objective).unwrap();
+
+        for line in Line::ALL.iter() {
+            assert_eq!(
+                rec.line_score(*line).as_f64(),
+                rec.distribution(*line).probability_at_least(720),
+                "{}",
+                line
+            );
+        }
+
+        let best = rec.line_score(rec.best_lines()[0]);
+        assert!(Line::ALL.iter().all(|l| rec.line_score(*l) <= best));
+    }
+
+    #[test]
+    fn min_variance_scores() {
+        let board = "1_8/_2_/9__".parse::<Board>().unwrap();
+        let rec =
+            Recommendation::from_board_for(&board, &PayoutTable::STANDARD, Objective::MinVariance)
+                .unwrap();
+
+        for line in Line::ALL.iter() {
+            let variance = rec.distribution(*line).variance();
+            let score = VARIANCE_CEILING as f64 - rec.line_score(*line).as_f64();
+
+            // Only off by the rounding of the squared deviations
+            assert!(
+                (score - variance).abs() < 1.0,
+                "{} {} {}",
+                line,
+                score,
+                variance
+            );
+        }
+
+        let best = rec.best_lines()[0];
+        for line in Line::ALL.iter() {
+            let key = |l: Line| (rec.line_score(l), rec.line_value(l));
+            assert!(key(*line) <= key(best), "{}", line);
+        }
+        assert!(Line::ALL
+            .iter()
+            .all(|l| rec.distribution(*l).variance() + 1.0 >= rec.distribution(best).variance()));
+    }
+
+    #[test]
+    fn objective_suggestions() {
+        let board = "1__/___/__5".parse::<Board>().unwrap();
+        let rec =
+            Recommendation::from_board_for(&board, &PayoutTable::STANDARD, Objective::Jackpot)
+                .unwrap();
+        let ranked = rec.ranked_reveals();
+
+        assert!(rec.suggestions().contains(&ranked[0].0));
+        for pos in rec.suggestions() {
+            let score = rec.reveal_score(*pos).unwrap();
+            assert!(ranked
+                .iter()
+                .all(|(p, _)| rec.reveal_score(*p).unwrap() <= score));
+        }
+    }
+
+    #[test]
+    fn lines() {
+        let board = "3__/_7_/__5".parse::<Board>().unwrap();
+        let rec =
+            Recommendation::from_board_for(&board, &PayoutTable::STANDARD, Objective::Jackpot)
+                .unwrap();
+
+        assert_eq!(rec.lines().iter().count(), NUM_LINES);
+        for (line, summary) in rec.lines().iter() {
+            assert_eq!(summary.value(), rec.line_value(line), "{}", line);
+            assert_eq!(summary.score(), rec.line_score(line), "{}", line);
+            assert_eq!(summary.distribution(), rec.distribution(line), "{}", line);
+        }
 
-        Ok(out)
+        // 3 + 7 + 5 is a guaranteed 15
+        let diagonal = &rec.lines()[Line::TopLeftBottomRight];
+        assert_eq!(diagonal.value(), ExpectedValue::new(180, 1));
+        assert_eq!(diagonal.distribution().sum_probability(15), 1.0);
     }
 }
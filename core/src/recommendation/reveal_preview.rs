@@ -0,0 +1,298 @@
+use super::Recommendation;
+use crate::solver::MAX_REVEALS;
+use crate::{
+    Board, BoardPosition, ExpectedValue, Line, Objective, PayoutTable, ValuedBoardPosition,
+};
+
+/// What the board would look like if a given value turned up in the previewed cell.
+///
+/// Serialized as `{"value": .., "probability": .., "recommendation": ..}`, with a value from 1 to
+/// 9 & a probability above 0 & at most 1.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(into = "RevealOutcomeRepr", try_from = "RevealOutcomeRepr")
+)]
+pub struct RevealOutcome {
+    value: u8,
+    probability: f64,
+    recommendation: Recommendation,
+}
+
+/// Serialized form of a [`RevealOutcome`]; see its docs for the fields
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RevealOutcomeRepr {
+    value: u8,
+    probability: f64,
+    recommendation: Recommendation,
+}
+
+#[cfg(feature = "serde")]
+impl From<RevealOutcome> for RevealOutcomeRepr {
+    fn from(outcome: RevealOutcome) -> Self {
+        Self {
+            value: outcome.value,
+            probability: outcome.probability,
+            recommendation: outcome.recommendation,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<RevealOutcomeRepr> for RevealOutcome {
+    type Error = &'static str;
+
+    fn try_from(repr: RevealOutcomeRepr) -> Result<Self, Self::Error> {
+        if !(1..=9).contains(&repr.value) {
+            return Err("Values go from 1 to 9");
+        } else if !(repr.probability > 0.0 && repr.probability <= 1.0) {
+            return Err("An outcome's probability must be above 0 & at most 1");
+        }
+
+        Ok(Self {
+            value: repr.value,
+            probability: repr.probability,
+            recommendation: repr.recommendation,
+        })
+    }
+}
+
+impl RevealOutcome {
+    #[inline]
+    pub fn value(&self) -> u8 {
+        self.value
+    }
+
+    /// Chance of the value turning up
+    #[inline]
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    /// Recommendation for the board with the value revealed
+    #[inline]
+    pub fn recommendation(&self) -> &Recommendation {
+        &self.recommendation
+    }
+
+    /// Expected payout of picking the given line after the reveal
+    #[inline]
+    pub fn line_value(&self, line: Line) -> ExpectedValue {
+        self.recommendation.line_value(line)
+    }
+
+    /// Where to scratch next after the reveal
+    #[inline]
+    pub fn suggestions(&self) -> &Vec<BoardPosition> {
+        self.recommendation.suggestions()
+    }
+}
+
+/// How the picture changes for each value that could turn up in a hidden cell.
+///
+/// Serialized as `{"position": .., "outcomes": [..]}`, with the [`RevealOutcome`]s ordered by
+/// value, lowest first, & no value turning up twice.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(into = "RevealPreviewRepr", try_from = "RevealPreviewRepr")
+)]
+pub struct RevealPreview {
+    position: BoardPosition,
+    outcomes: Vec<RevealOutcome>,
+}
+
+/// Serialized form of a [`RevealPreview`]; see its docs for the fields
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RevealPreviewRepr {
+    position: BoardPosition,
+    outcomes: Vec<RevealOutcome>,
+}
+
+#[cfg(feature = "serde")]
+impl From<RevealPreview> for RevealPreviewRepr {
+    fn from(preview: RevealPreview) -> Self {
+        Self {
+            position: preview.position,
+            outcomes: preview.outcomes,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<RevealPreviewRepr> for RevealPreview {
+    type Error = &'static str;
+
+    fn try_from(repr: RevealPreviewRepr) -> Result<Self, Self::Error> {
+        if !repr.outcomes.windows(2).all(|w| w[0].value < w[1].value) {
+            return Err("Outcomes must be ordered by value with no value twice");
+        }
+
+        Ok(Self {
+            position: repr.position,
+            outcomes: repr.outcomes,
+        })
+    }
+}
+
+impl RevealPreview {
+    /// Preview for the [standard payouts](PayoutTable::STANDARD)
+    #[inline]
+    pub fn from_board(board: &Board, pos: BoardPosition) -> Result<RevealPreview, &'static str> {
+        Self::from_board_for(board, pos, &PayoutTable::STANDARD, Objective::ExpectedValue)
+    }
+
+    /// Preview with every outcome's recommendation optimised for the given objective
+    pub fn from_board_for(
+        board: &Board,
+        pos: BoardPosition,
+        payouts: &PayoutTable,
+        objective: Objective,
+    ) -> Result<RevealPreview, &'static str> {
+        if board.len() >= MAX_REVEALS {
+            return Err("No scratches left");
+        } else if board.contains_position(pos) {
+            return Err("Cell already revealed");
+        }
+
+        let probability = 1.0 / board.remaining_capacity() as f64;
+        let outcomes = board
+            .available_selections()
+            .map(|value| {
+                let mut board = board.clone();
+                board
+                    .fill(ValuedBoardPosition::from_pos(value, pos))
+                    .map_err(|_| "Invalid reveal")?;
+                let recommendation = Recommendation::from_board_for(&board, payouts, objective)?;
+
+                Ok(RevealOutcome {
+                    value,
+                    probability,
+                    recommendation,
+                })
+            })
+            .collect::<Result<Vec<_>, &'static str>>()?;
+
+        Ok(RevealPreview {
+            position: pos,
+            outcomes,
+        })
+    }
+
+    /// The previewed cell
+    #[inline]
+    pub fn position(&self) -> BoardPosition {
+        self.position
+    }
+
+    /// Outcome of each value that could turn up, lowest value first
+    #[inline]
+    pub fn outcomes(&self) -> &[RevealOutcome] {
+        &self.outcomes
+    }
+
+    #[inline]
+    pub fn outcome(&self, value: u8) -> Option<&RevealOutcome> {
+        self.outcomes.iter().find(|o| o.value == value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let board = "1__/_5_/___".parse::<Board>().unwrap();
+        let preview = RevealPreview::from_board(&board, BoardPosition::new(2, 2)).unwrap();
+        let json = serde_json::to_string(&preview).unwrap();
+        let back = serde_json::from_str::<RevealPreview>(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+
+        let mut value = serde_json::to_value(&preview).unwrap();
+        value["outcomes"][0]["value"] = 0.into();
+        assert!(serde_json::from_value::<RevealPreview>(value).is_err());
+
+        let mut value = serde_json::to_value(&preview).unwrap();
+        value["outcomes"][0]["probability"] = 1.5.into();
+        assert!(serde_json::from_value::<RevealPreview>(value).is_err());
+
+        let mut value = serde_json::to_value(&preview).unwrap();
+        value["outcomes"][1]["value"] = 2.into();
+        assert!(serde_json::from_value::<RevealPreview>(value).is_err());
+    }
+
+    #[test]
+    fn outcomes() {
+        let board = "1__/_5_/___".parse::<Board>().unwrap();
+        let pos = BoardPosition::new(2, 2);
+        let preview = RevealPreview::from_board(&board, pos).unwrap();
+
+        let values: Vec<u8> = preview.outcomes().iter().map(|o| o.value()).collect();
+        assert_eq!(values, vec![2, 3, 4, 6, 7, 8, 9]);
+        assert!(preview
+            .outcomes()
+            .iter()
+            .all(|o| o.probability() == 1.0 / 7.0));
+
+        let mut revealed = board.clone();
+        revealed
+            .fill(ValuedBoardPosition::from_pos(9, pos))
+            .unwrap();
+        let exp = Recommendation::from_board(&revealed).unwrap();
+        let outcome = preview.outcome(9).unwrap();
+
+        assert_eq!(outcome.suggestions(), exp.suggestions());
+        for line in Line::iter() {
+            assert_eq!(outcome.line_value(line), exp.line_value(line), "{}", line);
+        }
+        // 1 + 5 + 9 is a sure 15
+        assert_eq!(
+            outcome.line_value(Line::TopLeftBottomRight),
+            ExpectedValue::new(180, 1)
+        );
+        assert!(preview.outcome(5).is_none());
+    }
+
+    #[test]
+    fn matches_current_recommendation() {
+        let board = "_4_/___/___".parse::<Board>().unwrap();
+        let pos = BoardPosition::new(0, 1);
+        let preview = RevealPreview::from_board(&board, pos).unwrap();
+        let current = Recommendation::from_board(&board).unwrap();
+
+        // Averaging over the outcomes gets back the current line values
+        for line in Line::iter() {
+            let total: u64 = preview
+                .outcomes()
+                .iter()
+                .map(|o| o.line_value(line).total())
+                .sum();
+
+            assert_eq!(total, current.line_value(line).total(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let board = "123/4__/___".parse::<Board>().unwrap();
+
+        assert_eq!(
+            RevealPreview::from_board(&board, BoardPosition::new(2, 2)).unwrap_err(),
+            "No scratches left"
+        );
+        assert_eq!(
+            RevealPreview::from_board(
+                &"1__/___/___".parse::<Board>().unwrap(),
+                BoardPosition::new(0, 0)
+            )
+            .unwrap_err(),
+            "Cell already revealed"
+        );
+    }
+}
@@ -0,0 +1,418 @@
+use super::line::stats::LineStats;
+use super::packed_board::PackedBoard;
+use super::recommendation::{validate_board, NO_REVEALS_YET};
+use super::{
+    Board, BoardPosition, Edit, Game, GameError, Line, Objective, PayoutTable, Phase,
+    Recommendation, ValuedBoardPosition,
+};
+
+/// A game in progress. Plays the [game](Game) by its rules while keeping the endings still
+/// consistent with the board, narrowing them down as cells get revealed instead of enumerating
+/// them from scratch, & the recommendation for the current board. Clearing or correcting a cell
+/// starts the narrowing over.
+#[derive(Debug)]
+pub struct Session {
+    game: Game,
+    objective: Objective,
+    /// The first `num_endings` are the ones consistent with the board
+    endings: Vec<PackedBoard>,
+    num_endings: usize,
+    /// Number of consistent endings before each of the board's fills, in fill order
+    history: Vec<usize>,
+    /// Line stats of the consistent endings after each of the board's fills, in fill order
+    stats: Vec<LineStats>,
+    recommendation: Result<Recommendation, &'static str>,
+}
+
+impl Session {
+    pub fn new(payouts: PayoutTable, objective: Objective) -> Self {
+        Self {
+            game: Game::new(payouts),
+            objective,
+            endings: Vec::new(),
+            num_endings: 0,
+            history: Vec::new(),
+            stats: Vec::new(),
+            recommendation: Err(NO_REVEALS_YET),
+        }
+    }
+
+    /// The game being played, for its [phase](Game::phase) & rules
+    #[inline]
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    #[inline]
+    pub fn board(&self) -> &Board {
+        self.game.board()
+    }
+
+    #[inline]
+    pub fn payouts(&self) -> &PayoutTable {
+        self.game.payouts()
+    }
+
+    #[inline]
+    pub fn objective(&self) -> Objective {
+        self.objective
+    }
+
+    /// Recommendation for the current board; an error if there's nothing to recommend yet or
+    /// anymore
+    #[inline]
+    pub fn recommendation(&self) -> Result<&Recommendation, &'static str> {
+        self.recommendation.as_ref().map_err(|e| *e)
+    }
+
+    /// Number of endings still possible; 0 before the first reveal
+    #[inline]
+    pub fn num_endings(&self) -> usize {
+        self.num_endings
+    }
+
+    /// Reveal a cell, following the game's [rules](Game::reveal). The board's left untouched on
+    /// error.
+    pub fn fill(&mut self, pos: ValuedBoardPosition) -> Result<Phase, GameError> {
+        let phase = self.game.reveal(pos)?;
+        self.push_fill(pos);
+        self.refresh();
+
+        Ok(phase)
+    }
+
+    /// Clear a revealed cell, returning what was in it
+    pub fn remove(&mut self, pos: BoardPosition) -> Result<ValuedBoardPosition, GameError> {
+        let out = self.game.remove(pos)?;
+        self.rebuild();
+
+        Ok(out)
+    }
+
+    /// Correct the value of a revealed cell, returning the previous one
+    pub fn replace(&mut self, pos: ValuedBoardPosition) -> Result<ValuedBoardPosition, GameError> {
+        let out = self.game.replace(pos)?;
+        self.rebuild();
+
+        Ok(out)
+    }
+
+    /// Pick a line once every scratch has been used; see [`Game::select_line`]
+    #[inline]
+    pub fn select_line(&mut self, line: Line, values: [u8; 3]) -> Result<u16, GameError> {
+        self.game.select_line(line, values)
+    }
+
+    /// Narrow the endings down to the ones consistent with the board's newest fill
+    fn push_fill(&mut self, pos: ValuedBoardPosition) {
+        self.history.push(self.num_endings);
+
+        match self.history.len() {
+            1 => {
+                self.endings.clear();
+                let mut stats = LineStats::default();
+                let first = PackedBoard::default().with(pos.position().index(), pos.value());
+                first.for_each_ending(|cells| {
+                    stats.add(cells);
+                    self.endings.push(PackedBoard::from_cells(cells));
+                });
+                self.num_endings = self.endings.len();
+                self.stats.push(stats);
+            }
+            _ => self.narrow(pos),
+        }
+    }
+
+    /// Narrow the endings down from scratch, replaying every fill
+    fn rebuild(&mut self) {
+        self.history.clear();
+        self.stats.clear();
+        self.num_endings = 0;
+
+        let fills: Vec<ValuedBoardPosition> = self.board().fills().to_vec();
+        for pos in fills {
+            self.push_fill(pos);
+        }
+        self.refresh();
+    }
+
+    /// Move the endings consistent with the newly revealed cell to the front, taking the ones it
+    /// rules out off the stats
+    fn narrow(&mut self, pos: ValuedBoardPosition) {
+        let (idx, value) = (pos.position().index(), pos.value());
+        let mut stats = self.stats.last().cloned().unwrap_or_default();
+        let mut kept = 0;
+        for i in 0..self.num_endings {
+            if self.endings[i].value_at(idx) == value {
+                self.endings.swap(kept, i);
+                kept += 1;
+            } else {
+                stats.remove(&self.endings[i].cells());
+            }
+        }
+
+        self.num_endings = kept;
+        self.stats.push(stats);
+    }
+
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        self.game.can_undo()
+    }
+
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        self.game.can_redo()
+    }
+
+    /// Take back the most recent edit, returning it; `None` if there's nothing to undo
+    pub fn undo(&mut self) -> Result<Option<Edit>, GameError> {
+        let out = match self.game.undo()? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        match out {
+            // Undoing a fill always takes off the newest one. The endings it ruled out are still
+            // in the buffer, right behind the kept ones.
+            Edit::Fill(_) => {
+                self.num_endings = self.history.pop().unwrap_or_default();
+                self.stats.pop();
+                self.refresh();
+            }
+            _ => self.rebuild(),
+        }
+
+        Ok(Some(out))
+    }
+
+    /// Make the most recently undone edit again, returning it; `None` if there's nothing to redo
+    pub fn redo(&mut self) -> Result<Option<Edit>, GameError> {
+        let out = match self.game.redo()? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        match out {
+            Edit::Fill(pos) => {
+                self.push_fill(pos);
+                self.refresh();
+            }
+            _ => self.rebuild(),
+        }
+
+        Ok(Some(out))
+    }
+
+    /// Start a new game with the same settings
+    pub fn reset(&mut self) {
+        self.game.reset();
+        self.history.clear();
+        self.stats.clear();
+        self.num_endings = 0;
+        self.refresh();
+    }
+
+    pub fn set_payouts(&mut self, payouts: PayoutTable) {
+        self.game.set_payouts(payouts);
+        self.refresh();
+    }
+
+    pub fn set_objective(&mut self, objective: Objective) {
+        self.objective = objective;
+        self.refresh();
+    }
+
+    /// Recommend from the stats of the current board's endings
+    fn refresh(&mut self) {
+        let board = self.game.board();
+        let stats = self.stats.last();
+        self.recommendation = validate_board(board).and_then(|_| {
+            let stats = stats.ok_or(NO_REVEALS_YET)?;
+
+            Ok(Recommendation::from_stats(
+                board,
+                stats,
+                self.game.payouts(),
+                self.objective,
+            ))
+        });
+    }
+}
+
+impl Default for Session {
+    #[inline]
+    fn default() -> Self {
+        Self::new(PayoutTable::STANDARD, Objective::ExpectedValue)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FillFailure;
+
+    fn assert_same(a: &Recommendation, b: &Recommendation) {
+        for line in Line::ALL.iter() {
+            assert_eq!(a.line_value(*line), b.line_value(*line), "{}", line);
+            assert_eq!(a.line_score(*line), b.line_score(*line), "{}", line);
+            assert_eq!(a.distribution(*line), b.distribution(*line), "{}", line);
+        }
+        assert_eq!(a.suggestions(), b.suggestions());
+        assert_eq!(a.heuristic_suggestions(), b.heuristic_suggestions());
+        assert_eq!(a.best_lines(), b.best_lines());
+    }
+
+    fn assert_matches_board(session: &Session) {
+        let exp =
+            Recommendation::from_board_for(session.board(), session.payouts(), session.objective())
+                .unwrap();
+
+        assert_same(session.recommendation().unwrap(), &exp);
+    }
+
+    #[test]
+    fn narrows_endings() {
+        let mut session = Session::default();
+        assert_eq!(session.recommendation().unwrap_err(), NO_REVEALS_YET);
+        assert_eq!(session.num_endings(), 0);
+
+        let fills = [
+            ValuedBoardPosition::from_u8(6, 0, 2),
+            ValuedBoardPosition::from_u8(2, 1, 1),
+            ValuedBoardPosition::from_u8(9, 2, 1),
+            ValuedBoardPosition::from_u8(1, 0, 0),
+        ];
+        let counts = [40320, 5040, 720, 120];
+        for (fill, count) in fills.iter().zip(counts.iter()) {
+            session.fill(*fill).unwrap();
+
+            assert_eq!(session.num_endings(), *count);
+            assert_matches_board(&session);
+        }
+    }
+
+    #[test]
+    fn undo() {
+        let mut session = Session::default();
+        let first = ValuedBoardPosition::from_u8(3, 1, 0);
+        let second = ValuedBoardPosition::from_u8(7, 2, 2);
+        session.fill(first).unwrap();
+        session.fill(second).unwrap();
+
+        assert_eq!(session.undo(), Ok(Some(Edit::Fill(second))));
+        assert_eq!(session.num_endings(), 40320);
+        assert_matches_board(&session);
+
+        // A different reveal from the restored set
+        session.fill(ValuedBoardPosition::from_u8(8, 0, 1)).unwrap();
+        assert_eq!(session.num_endings(), 5040);
+        assert_matches_board(&session);
+
+        session.undo().unwrap();
+        assert_eq!(session.undo(), Ok(Some(Edit::Fill(first))));
+        assert_eq!(session.undo(), Ok(None));
+        assert!(session.board().is_empty());
+        assert!(session.recommendation().is_err());
+    }
+
+    #[test]
+    fn failed_fill() {
+        let mut session = Session::default();
+        session.fill(ValuedBoardPosition::from_u8(3, 1, 0)).unwrap();
+
+        assert_eq!(
+            session.fill(ValuedBoardPosition::from_u8(3, 2, 0)),
+            Err(GameError::Fill(FillFailure::ValueAlreadyContained))
+        );
+        assert_eq!(session.board().len(), 1);
+        assert_eq!(session.num_endings(), 40320);
+    }
+
+    #[test]
+    fn follows_the_rules() {
+        let mut session = Session::default();
+        assert_eq!(
+            session.select_line(Line::Row0, [1, 2, 3]),
+            Err(GameError::AwaitingInitialReveal)
+        );
+
+        let fills = [
+            ValuedBoardPosition::from_u8(1, 0, 0),
+            ValuedBoardPosition::from_u8(5, 1, 1),
+            ValuedBoardPosition::from_u8(2, 1, 0),
+        ];
+        for fill in fills.iter() {
+            session.fill(*fill).unwrap();
+        }
+        assert_eq!(
+            session.fill(ValuedBoardPosition::from_u8(9, 2, 2)),
+            Ok(Phase::LineSelection)
+        );
+        assert_eq!(
+            session.fill(ValuedBoardPosition::from_u8(3, 2, 0)),
+            Err(GameError::NoScratchesLeft)
+        );
+        assert_eq!(session.board().len(), 4);
+        assert_eq!(session.num_endings(), 120);
+        assert_matches_board(&session);
+
+        assert_eq!(session.select_line(Line::Row0, [1, 2, 3]), Ok(10000));
+        assert_eq!(session.undo(), Err(GameError::AlreadyResolved));
+
+        session.reset();
+        assert_eq!(session.game().phase(), Phase::InitialReveal);
+        assert_eq!(session.num_endings(), 0);
+    }
+
+    #[test]
+    fn settings() {
+        let mut session = Session::default();
+        session.fill(ValuedBoardPosition::from_u8(1, 0, 0)).unwrap();
+        session.fill(ValuedBoardPosition::from_u8(2, 1, 1)).unwrap();
+        session.fill(ValuedBoardPosition::from_u8(5, 2, 1)).unwrap();
+
+        session.set_payouts(PayoutTable::STANDARD.with_bonus(20));
+        assert_matches_board(&session);
+        session.set_objective(Objective::Jackpot);
+        assert_matches_board(&session);
+
+        session.reset();
+        assert!(session.board().is_empty());
+        assert_eq!(session.objective(), Objective::Jackpot);
+    }
+
+    #[test]
+    fn corrections() {
+        let mut session = Session::default();
+        session.fill(ValuedBoardPosition::from_u8(3, 1, 0)).unwrap();
+        session.fill(ValuedBoardPosition::from_u8(7, 2, 2)).unwrap();
+        session.fill(ValuedBoardPosition::from_u8(1, 0, 1)).unwrap();
+
+        session.remove(BoardPosition::new(1, 0)).unwrap();
+        assert_eq!(session.num_endings(), 5040);
+        assert_matches_board(&session);
+
+        session
+            .replace(ValuedBoardPosition::from_u8(2, 0, 1))
+            .unwrap();
+        assert_eq!(session.num_endings(), 5040);
+        assert_matches_board(&session);
+
+        // Back to before the correction, then back to before the removal
+        session.undo().unwrap();
+        session.undo().unwrap();
+        assert_eq!(session.board().len(), 3);
+        assert_eq!(session.num_endings(), 720);
+        assert_matches_board(&session);
+
+        session.undo().unwrap();
+        assert_eq!(session.num_endings(), 5040);
+        assert_matches_board(&session);
+
+        assert_eq!(
+            session.redo(),
+            Ok(Some(Edit::Fill(ValuedBoardPosition::from_u8(1, 0, 1))))
+        );
+        assert_eq!(session.num_endings(), 720);
+        assert_matches_board(&session);
+    }
+}
@@ -0,0 +1,274 @@
+pub(crate) use evaluation::expected_value_from;
+#[cfg(feature = "parallel")]
+pub use evaluation::par_evaluate;
+pub use evaluation::{evaluate, PolicyEvaluation};
+pub use report::SimulationReport;
+pub use simulation_error::SimulationError;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use super::{BoardPosition, Game, PayoutTable, Strategy, ValuedBoardPosition};
+
+mod evaluation;
+mod report;
+mod simulation_error;
+
+/// A board with every value known upfront, as dealt by the game
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct HiddenBoard {
+    values: [u8; 9],
+}
+
+impl HiddenBoard {
+    /// Board from the values of every cell, ordered by [position index](BoardPosition::index).
+    /// `None` unless every value from 1 to 9 appears exactly once.
+    pub fn new(values: [u8; 9]) -> Option<Self> {
+        let mut seen = 0u16;
+        for v in values.iter() {
+            if !(1..=9).contains(v) || seen & (1 << v) != 0 {
+                return None;
+            }
+            seen |= 1 << v;
+        }
+
+        Some(Self { values })
+    }
+
+    /// A uniformly random board
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut values = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        values.shuffle(rng);
+
+        Self { values }
+    }
+
+    #[inline]
+    pub fn value_at(&self, pos: BoardPosition) -> u8 {
+        self.values[pos.index() as usize]
+    }
+
+    /// Scratch the given cell in the game
+    #[inline]
+    fn reveal(&self, game: &mut Game, pos: BoardPosition) -> Result<(), SimulationError> {
+        game.reveal(ValuedBoardPosition::from_pos(self.value_at(pos), pos))
+            .map_err(SimulationError::from_reveal)?;
+
+        Ok(())
+    }
+
+    /// Play the board out from the given starting cell & return the MGP won. The [game](Game)
+    /// enforces the scratch limit; a strategy may still pick its line before using up every
+    /// scratch.
+    pub fn play<S: Strategy + ?Sized>(
+        &self,
+        start: BoardPosition,
+        strategy: &mut S,
+        payouts: &PayoutTable,
+    ) -> Result<u16, SimulationError> {
+        let mut game = Game::new(*payouts);
+        self.reveal(&mut game, start)?;
+
+        while let Some(pos) = strategy.choose_reveal(game.board()) {
+            self.reveal(&mut game, pos)?;
+        }
+
+        let line = strategy
+            .choose_line(game.board())
+            .ok_or(SimulationError::NoLine)?;
+        let sum = line.positions().iter().map(|pos| self.value_at(*pos)).sum();
+
+        Ok(payouts.payout(sum))
+    }
+}
+
+/// Plays random boards with a strategy to see how it fares
+#[derive(Debug, Clone)]
+pub struct Simulator<R = StdRng> {
+    rng: R,
+    payouts: PayoutTable,
+}
+
+impl<R: Rng> Simulator<R> {
+    #[inline]
+    pub fn new(rng: R, payouts: PayoutTable) -> Self {
+        Self { rng, payouts }
+    }
+
+    /// Deal a random board, reveal a random starting cell & play it out
+    pub fn play<S: Strategy + ?Sized>(&mut self, strategy: &mut S) -> Result<u16, SimulationError> {
+        let board = HiddenBoard::random(&mut self.rng);
+        let start = BoardPosition::from_index(self.rng.gen_range(0..9));
+
+        board.play(start, strategy, &self.payouts)
+    }
+
+    /// Play the given number of games
+    pub fn run<S: Strategy + ?Sized>(
+        &mut self,
+        strategy: &mut S,
+        num_games: u64,
+    ) -> Result<SimulationReport, SimulationError> {
+        let mut report = SimulationReport::default();
+        for _ in 0..num_games {
+            report.add(self.play(strategy)?);
+        }
+
+        Ok(report)
+    }
+}
+
+impl Simulator<StdRng> {
+    /// Simulator dealing the same boards every time for the same seed
+    #[inline]
+    pub fn seeded(seed: u64, payouts: PayoutTable) -> Self {
+        Self::new(StdRng::seed_from_u64(seed), payouts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "precomputed")]
+    use crate::OptimalStrategy;
+    use crate::{Board, ExpectedValue, HeuristicStrategy, Line, RandomStrategy};
+
+    /// Always picks the same line without scratching anything
+    struct FixedLine(Line);
+
+    impl Strategy for FixedLine {
+        fn choose_reveal(&mut self, _: &Board) -> Option<BoardPosition> {
+            None
+        }
+
+        fn choose_line(&mut self, _: &Board) -> Option<Line> {
+            Some(self.0)
+        }
+    }
+
+    /// Keeps scratching the same cell
+    struct Stubborn;
+
+    impl Strategy for Stubborn {
+        fn choose_reveal(&mut self, _: &Board) -> Option<BoardPosition> {
+            Some(BoardPosition::from_index(0))
+        }
+
+        fn choose_line(&mut self, _: &Board) -> Option<Line> {
+            Some(Line::Row0)
+        }
+    }
+
+    /// Ignores the scratch limit
+    struct RevealAll;
+
+    impl Strategy for RevealAll {
+        fn choose_reveal(&mut self, board: &Board) -> Option<BoardPosition> {
+            (0..9)
+                .map(BoardPosition::from_index)
+                .find(|pos| !board.contains_position(*pos))
+        }
+
+        fn choose_line(&mut self, _: &Board) -> Option<Line> {
+            Some(Line::Row0)
+        }
+    }
+
+    #[test]
+    fn hidden_board_validation() {
+        assert!(HiddenBoard::new([1, 2, 3, 4, 5, 6, 7, 8, 9]).is_some());
+        assert!(HiddenBoard::new([1, 1, 3, 4, 5, 6, 7, 8, 9]).is_none());
+        assert!(HiddenBoard::new([0, 2, 3, 4, 5, 6, 7, 8, 9]).is_none());
+    }
+
+    #[test]
+    fn play_fixed_board() {
+        let board = HiddenBoard::new([1, 9, 8, 7, 2, 6, 5, 4, 3]).unwrap();
+        let start = BoardPosition::from_index(4);
+        let payouts = PayoutTable::STANDARD;
+
+        // 1 + 2 + 3
+        let won = board.play(start, &mut FixedLine(Line::TopLeftBottomRight), &payouts);
+        assert_eq!(won.unwrap(), 10000);
+        // 1 + 9 + 8
+        let won = board.play(start, &mut FixedLine(Line::Row0), &payouts);
+        assert_eq!(won.unwrap(), 119);
+    }
+
+    #[test]
+    fn bad_strategy() {
+        let mut sim = Simulator::seeded(1, PayoutTable::STANDARD);
+
+        assert!(matches!(
+            sim.run(&mut Stubborn, 10),
+            Err(SimulationError::Fill(_))
+        ));
+    }
+
+    #[test]
+    fn reveal_limit() {
+        let board = HiddenBoard::new([1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+        let won = board.play(
+            BoardPosition::from_index(0),
+            &mut RevealAll,
+            &PayoutTable::STANDARD,
+        );
+
+        assert_eq!(won, Err(SimulationError::TooManyReveals));
+    }
+
+    #[test]
+    fn same_seed_same_report() {
+        let run = || {
+            let mut sim = Simulator::seeded(3, PayoutTable::STANDARD);
+            sim.run(&mut RandomStrategy::seeded(4), 500).unwrap()
+        };
+        let report = run();
+
+        assert_eq!(report, run());
+        assert_eq!(report.num_games(), 500);
+        assert_eq!(report.histogram().values().sum::<u64>(), 500);
+    }
+
+    #[test]
+    fn heuristic_beats_random() {
+        let payouts = PayoutTable::STANDARD;
+        let heuristic = evaluate(&mut HeuristicStrategy::default(), &payouts).unwrap();
+        let random = evaluate(&mut RandomStrategy::seeded(5), &payouts).unwrap();
+
+        // ~1411.29 MGP across every board & starting reveal
+        assert_eq!(
+            heuristic.expected_value(),
+            ExpectedValue::new(4609161876, 3265920)
+        );
+        assert!(heuristic.expected_value() > random.expected_value());
+    }
+
+    // Live solving would make this far too slow
+    #[cfg(feature = "precomputed")]
+    #[test]
+    fn optimal_matches_evaluation() {
+        const NUM_GAMES: u64 = 200_000;
+
+        let payouts = PayoutTable::STANDARD;
+        let exact = evaluate(&mut OptimalStrategy::default(), &payouts).unwrap();
+        let mut sim = Simulator::seeded(6, payouts);
+        let report = sim.run(&mut OptimalStrategy::default(), NUM_GAMES).unwrap();
+
+        assert_eq!(report.num_games(), NUM_GAMES);
+        assert_eq!(report.histogram().values().sum::<u64>(), NUM_GAMES);
+
+        // ~1484.98 MGP
+        let expected = exact.expected_value().as_f64();
+        assert!((expected - 1484.98).abs() < 0.01, "{}", expected);
+        let std_err = report.std_dev() / (NUM_GAMES as f64).sqrt();
+        assert!(
+            (report.mean() - expected).abs() < 4.0 * std_err,
+            "{} vs {} (standard error {})",
+            report.mean(),
+            expected,
+            std_err
+        );
+    }
+}
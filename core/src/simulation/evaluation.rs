@@ -0,0 +1,313 @@
+use super::SimulationError;
+use crate::{
+    Board, BoardPosition, ExpectedValue, Game, PayoutTable, Strategy, ValuedBoardPosition,
+};
+
+/// Number of ways the 8 cells left after the starting reveal can be filled in
+const ENDINGS_PER_START: u64 = 40320;
+
+/// Exact value of a strategy across every hidden board & starting reveal.
+///
+/// Serialized as `{"totals": [..]}`, the payouts summed across every ending for each starting
+/// position by [index](BoardPosition::index), then for each starting value from 1 to 9.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolicyEvaluation {
+    /// Payouts summed across every ending, indexed by starting position, then starting value - 1
+    totals: [[u64; 9]; 9],
+}
+
+impl PolicyEvaluation {
+    /// Expected MGP of a game
+    pub fn expected_value(&self) -> ExpectedValue {
+        let total = self.totals.iter().flatten().sum();
+
+        ExpectedValue::new(total, ENDINGS_PER_START * 81)
+    }
+
+    /// Expected MGP when the game reveals the given cell first
+    pub fn by_start_position(&self, pos: BoardPosition) -> ExpectedValue {
+        let total = self.totals[pos.index() as usize].iter().sum();
+
+        ExpectedValue::new(total, ENDINGS_PER_START * 9)
+    }
+
+    /// Expected MGP when the game reveals the given value first
+    pub fn by_start_value(&self, value: u8) -> ExpectedValue {
+        debug_assert!((1..=9).contains(&value), "Invalid value {}", value);
+
+        let total = self.totals.iter().map(|t| t[value as usize - 1]).sum();
+
+        ExpectedValue::new(total, ENDINGS_PER_START * 9)
+    }
+
+    /// Expected MGP when the game reveals the given value at the given cell first
+    pub fn by_start(&self, pos: BoardPosition, value: u8) -> ExpectedValue {
+        debug_assert!((1..=9).contains(&value), "Invalid value {}", value);
+
+        ExpectedValue::new(
+            self.totals[pos.index() as usize][value as usize - 1],
+            ENDINGS_PER_START,
+        )
+    }
+}
+
+/// Compute the exact expected MGP of a strategy by playing it against all 9! hidden boards with
+/// every possible starting reveal.
+///
+/// Boards revealing the same values in the same cells are indistinguishable to the player, so the
+/// strategy only gets asked once per distinct situation; randomised strategies get evaluated on
+/// a single draw of their choices.
+pub fn evaluate<S: Strategy + ?Sized>(
+    strategy: &mut S,
+    payouts: &PayoutTable,
+) -> Result<PolicyEvaluation, SimulationError> {
+    let mut totals = [[0u64; 9]; 9];
+
+    for (idx, pos_totals) in totals.iter_mut().enumerate() {
+        for (value, total) in (1u8..).zip(pos_totals.iter_mut()) {
+            *total = walk(strategy, &start_game(idx as u8, value, payouts)?)?;
+        }
+    }
+
+    Ok(PolicyEvaluation { totals })
+}
+
+/// [`evaluate`], with the 81 starting reveals spread across threads.
+///
+/// Every start gets played by its own clone of the strategy, so a randomised strategy draws its
+/// choices independently for each start & won't match [`evaluate`] with the same seed.
+#[cfg(feature = "parallel")]
+pub fn par_evaluate<S: Strategy + Clone + Sync>(
+    strategy: &S,
+    payouts: &PayoutTable,
+) -> Result<PolicyEvaluation, SimulationError> {
+    use rayon::prelude::*;
+
+    let flat = (0..81u8)
+        .into_par_iter()
+        .map(|start| {
+            let game = start_game(start / 9, start % 9 + 1, payouts)?;
+            walk(&mut strategy.clone(), &game)
+        })
+        .collect::<Result<Vec<u64>, SimulationError>>()?;
+
+    let mut totals = [[0u64; 9]; 9];
+    for (pos_totals, chunk) in totals.iter_mut().zip(flat.chunks(9)) {
+        pos_totals.copy_from_slice(chunk);
+    }
+
+    Ok(PolicyEvaluation { totals })
+}
+
+/// Game with just its starting reveal
+fn start_game(idx: u8, value: u8, payouts: &PayoutTable) -> Result<Game, SimulationError> {
+    let mut game = Game::new(*payouts);
+    game.reveal(ValuedBoardPosition::from_pos(
+        value,
+        BoardPosition::from_index(idx),
+    ))
+    .map_err(SimulationError::from_reveal)?;
+
+    Ok(game)
+}
+
+/// Exact expected MGP of playing on from the given board with the strategy
+pub(crate) fn expected_value_from<S: Strategy + ?Sized>(
+    strategy: &mut S,
+    board: &Board,
+    payouts: &PayoutTable,
+) -> Result<ExpectedValue, SimulationError> {
+    let total = walk(strategy, &Game::from_board(board.clone(), *payouts))?;
+    let num_endings = factorial(board.remaining_capacity() as u64);
+
+    Ok(ExpectedValue::new(total, num_endings))
+}
+
+/// Payout summed across every ending of the game's board, playing on with the strategy
+fn walk<S: Strategy + ?Sized>(strategy: &mut S, game: &Game) -> Result<u64, SimulationError> {
+    let board = game.board();
+    if let Some(pos) = strategy.choose_reveal(board) {
+        let mut total = 0;
+        for value in board.available_selections() {
+            let mut next = game.clone();
+            next.reveal(ValuedBoardPosition::from_pos(value, pos))
+                .map_err(SimulationError::from_reveal)?;
+            total += walk(strategy, &next)?;
+        }
+
+        return Ok(total);
+    }
+
+    let line = strategy.choose_line(board).ok_or(SimulationError::NoLine)?;
+
+    let mut known_sum = 0;
+    let mut num_unknown = 0;
+    for pos in line.positions().iter() {
+        match board.find(*pos) {
+            Some(vbp) => known_sum += vbp.value(),
+            None => num_unknown += 1,
+        }
+    }
+
+    let remaining: Vec<u8> = board.available_selections().collect();
+    // Cells outside the line can be filled in any order
+    let rest = factorial(remaining.len() as u64 - num_unknown);
+
+    Ok(line_total(&remaining, 0, known_sum, num_unknown, game.payouts()) * rest)
+}
+
+/// Payout summed across every way of filling in the line's unknown cells from the remaining values
+fn line_total(
+    remaining: &[u8],
+    used: u16,
+    sum: u8,
+    num_unknown: u64,
+    payouts: &PayoutTable,
+) -> u64 {
+    if num_unknown == 0 {
+        return payouts.payout(sum) as u64;
+    }
+
+    remaining
+        .iter()
+        .filter(|v| used & (1 << **v) == 0)
+        .map(|v| {
+            line_total(
+                remaining,
+                used | (1 << *v),
+                sum + v,
+                num_unknown - 1,
+                payouts,
+            )
+        })
+        .sum()
+}
+
+#[inline]
+fn factorial(n: u64) -> u64 {
+    (1..=n).product()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::payouts::payout_for_points;
+    use crate::solver::MAX_REVEALS;
+    #[cfg(feature = "parallel")]
+    use crate::HeuristicStrategy;
+    use crate::Line;
+
+    /// Scratches cells in index order & always picks the top row
+    #[derive(Clone)]
+    struct TopRow {
+        reveal: bool,
+    }
+
+    impl Strategy for TopRow {
+        fn choose_reveal(&mut self, board: &Board) -> Option<BoardPosition> {
+            if !self.reveal || board.len() >= MAX_REVEALS {
+                return None;
+            }
+
+            (0..9)
+                .map(BoardPosition::from_index)
+                .find(|pos| !board.contains_position(*pos))
+        }
+
+        fn choose_line(&mut self, _: &Board) -> Option<Line> {
+            Some(Line::Row0)
+        }
+    }
+
+    /// Payouts of every 3 value combination
+    fn combination_payouts() -> Vec<u16> {
+        let mut out = Vec::with_capacity(84);
+        for a in 1..=9 {
+            for b in (a + 1)..=9 {
+                for c in (b + 1)..=9 {
+                    out.push(payout_for_points(a + b + c));
+                }
+            }
+        }
+
+        out
+    }
+
+    #[test]
+    fn fixed_line() {
+        let payouts = combination_payouts();
+        let exp = ExpectedValue::new(payouts.iter().map(|v| *v as u64).sum(), 84);
+
+        for reveal in [false, true].iter() {
+            let eval = evaluate(&mut TopRow { reveal: *reveal }, &PayoutTable::STANDARD).unwrap();
+
+            assert_eq!(eval.expected_value(), exp, "{}", reveal);
+        }
+    }
+
+    #[test]
+    fn breakdown() {
+        let eval = evaluate(&mut TopRow { reveal: false }, &PayoutTable::STANDARD).unwrap();
+
+        // A 1 in the top row's known; the other 2 come from 2..=9
+        let mut total = 0u64;
+        let mut count = 0u64;
+        for a in 2..=9 {
+            for b in (a + 1)..=9 {
+                total += payout_for_points(1 + a + b) as u64;
+                count += 1;
+            }
+        }
+        let top_left = BoardPosition::new(0, 0);
+        assert_eq!(eval.by_start(top_left, 1), ExpectedValue::new(total, count));
+
+        // Starting positions & values are all equally likely
+        let mut by_pos = 0;
+        let mut by_value = 0;
+        for i in 0..9 {
+            by_pos += eval.by_start_position(BoardPosition::from_index(i)).total();
+            by_value += eval.by_start_value(i + 1).total();
+        }
+        assert_eq!(by_pos, eval.expected_value().total());
+        assert_eq!(by_value, eval.expected_value().total());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_serial() {
+        let payouts = PayoutTable::STANDARD.with_bonus(10);
+
+        let serial = evaluate(&mut HeuristicStrategy::default(), &payouts).unwrap();
+        let parallel = par_evaluate(&HeuristicStrategy::default(), &payouts).unwrap();
+        assert_eq!(parallel, serial);
+
+        for reveal in [false, true].iter() {
+            let serial = evaluate(&mut TopRow { reveal: *reveal }, &payouts).unwrap();
+            let parallel = par_evaluate(&TopRow { reveal: *reveal }, &payouts).unwrap();
+            assert_eq!(parallel, serial, "{}", reveal);
+        }
+    }
+
+    #[test]
+    fn too_many_reveals() {
+        struct RevealAll;
+
+        impl Strategy for RevealAll {
+            fn choose_reveal(&mut self, board: &Board) -> Option<BoardPosition> {
+                (0..9)
+                    .map(BoardPosition::from_index)
+                    .find(|pos| !board.contains_position(*pos))
+            }
+
+            fn choose_line(&mut self, _: &Board) -> Option<Line> {
+                None
+            }
+        }
+
+        assert_eq!(
+            evaluate(&mut RevealAll, &PayoutTable::STANDARD),
+            Err(SimulationError::TooManyReveals)
+        );
+    }
+}
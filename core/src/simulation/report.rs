@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+
+/// MGP won across a number of simulated games.
+///
+/// Serialized as `{"histogram": {..}}`, mapping each payout to the number of games won with it;
+/// everything else follows from the histogram.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(into = "SimulationReportRepr", from = "SimulationReportRepr")
+)]
+pub struct SimulationReport {
+    histogram: BTreeMap<u16, u64>,
+    num_games: u64,
+    total: u64,
+}
+
+/// Serialized form of a [`SimulationReport`]; see its docs for the fields
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SimulationReportRepr {
+    histogram: BTreeMap<u16, u64>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SimulationReport> for SimulationReportRepr {
+    fn from(report: SimulationReport) -> Self {
+        Self {
+            histogram: report.histogram,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<SimulationReportRepr> for SimulationReport {
+    fn from(mut repr: SimulationReportRepr) -> Self {
+        repr.histogram.retain(|_, count| *count != 0);
+        let num_games = repr.histogram.values().sum();
+        let total = repr
+            .histogram
+            .iter()
+            .map(|(payout, count)| *payout as u64 * *count)
+            .sum();
+
+        Self {
+            histogram: repr.histogram,
+            num_games,
+            total,
+        }
+    }
+}
+
+impl SimulationReport {
+    #[inline]
+    pub(crate) fn add(&mut self, payout: u16) {
+        *self.histogram.entry(payout).or_insert(0) += 1;
+        self.num_games += 1;
+        self.total += payout as u64;
+    }
+
+    #[inline]
+    pub fn num_games(&self) -> u64 {
+        self.num_games
+    }
+
+    /// Number of games won for each payout, lowest payout first
+    #[inline]
+    pub fn histogram(&self) -> &BTreeMap<u16, u64> {
+        &self.histogram
+    }
+
+    /// Average MGP won per game
+    pub fn mean(&self) -> f64 {
+        match self.num_games {
+            0 => 0.0,
+            n => self.total as f64 / n as f64,
+        }
+    }
+
+    pub fn variance(&self) -> f64 {
+        if self.num_games == 0 {
+            return 0.0;
+        }
+
+        let mean = self.mean();
+        let sq_total: f64 = self
+            .histogram
+            .iter()
+            .map(|(payout, count)| {
+                let diff = *payout as f64 - mean;
+                diff * diff * *count as f64
+            })
+            .sum();
+
+        sq_total / self.num_games as f64
+    }
+
+    #[inline]
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let mut report = SimulationReport::default();
+        for payout in [36, 36, 72, 10000].iter() {
+            report.add(*payout);
+        }
+        let mean = (36.0 + 36.0 + 72.0 + 10000.0) / 4.0;
+        let variance = [36.0, 36.0, 72.0, 10000.0]
+            .iter()
+            .map(|v: &f64| (v - mean) * (v - mean))
+            .sum::<f64>()
+            / 4.0;
+
+        assert_eq!(report.num_games(), 4);
+        assert_eq!(report.mean(), mean);
+        assert!((report.variance() - variance).abs() < 1e-6);
+        assert!((report.std_dev() - variance.sqrt()).abs() < 1e-6);
+        assert_eq!(
+            report.histogram().iter().collect::<Vec<_>>(),
+            vec![(&36, &2), (&72, &1), (&10000, &1)]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let mut report = SimulationReport::default();
+        for payout in [36, 36, 72, 10000].iter() {
+            report.add(*payout);
+        }
+        let json = serde_json::to_string(&report).unwrap();
+
+        assert_eq!(json, r#"{"histogram":{"36":2,"72":1,"10000":1}}"#);
+        assert_eq!(
+            serde_json::from_str::<SimulationReport>(&json).unwrap(),
+            report
+        );
+        assert_eq!(
+            serde_json::from_str::<SimulationReport>(r#"{"histogram":{"36":0}}"#).unwrap(),
+            SimulationReport::default()
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let report = SimulationReport::default();
+
+        assert_eq!(report.mean(), 0.0);
+        assert_eq!(report.std_dev(), 0.0);
+    }
+}
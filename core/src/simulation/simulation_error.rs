@@ -0,0 +1,66 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{FillFailure, GameError};
+
+/// A strategy made a move the game doesn't allow
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SimulationError {
+    /// Tried to scratch a cell that's already been revealed
+    Fill(FillFailure),
+    /// Tried to scratch more cells than the game allows
+    TooManyReveals,
+    /// Didn't pick a line at the end of the game
+    NoLine,
+    /// The game turned down a reveal for a reason no strategy should be able to cause
+    Game(GameError),
+}
+
+impl SimulationError {
+    /// Why the game turned down a strategy's reveal. The revealed values come from a valid board
+    /// & games don't get resolved mid-simulation, so scratching a revealed cell or one too many
+    /// should be the only ways to get there.
+    pub(crate) fn from_reveal(e: GameError) -> Self {
+        match e {
+            GameError::Fill(e) => Self::Fill(e),
+            GameError::NoScratchesLeft => Self::TooManyReveals,
+            GameError::AwaitingInitialReveal
+            | GameError::ScratchesLeft(_)
+            | GameError::LineMismatch
+            | GameError::AlreadyResolved => Self::Game(e),
+        }
+    }
+}
+
+impl Display for SimulationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fill(e) => write!(f, "Strategy made an invalid reveal: {}", e),
+            Self::TooManyReveals => f.write_str("Strategy scratched too many cells"),
+            Self::NoLine => f.write_str("Strategy didn't pick a line"),
+            Self::Game(e) => write!(f, "Game turned down a reveal: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_reveal() {
+        assert_eq!(
+            SimulationError::from_reveal(GameError::Fill(FillFailure::PositionAlreadyFilled)),
+            SimulationError::Fill(FillFailure::PositionAlreadyFilled)
+        );
+        assert_eq!(
+            SimulationError::from_reveal(GameError::NoScratchesLeft),
+            SimulationError::TooManyReveals
+        );
+        assert_eq!(
+            SimulationError::from_reveal(GameError::AlreadyResolved),
+            SimulationError::Game(GameError::AlreadyResolved)
+        );
+    }
+}
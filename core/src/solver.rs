@@ -0,0 +1,517 @@
+use std::collections::HashMap;
+use std::iter::Sum;
+use std::ops::Add;
+
+use super::line::stats::{LineStats, SumCounts};
+use super::objective::Scorer;
+use super::packed_board::PackedBoard;
+use super::payouts::MAX_SUM;
+use super::{Board, BoardPosition, ExpectedValue, Objective, PayoutTable};
+
+/// Cells revealed before a line gets picked: the one chosen by the game + 3 scratches
+pub const MAX_REVEALS: u8 = 4;
+
+const NUM_CELLS: u8 = 9;
+
+/// Boards with fewer fills than this get their cells solved in parallel. Past that the memo
+/// sharing of the serial path outweighs the extra threads.
+#[cfg(feature = "parallel")]
+const PARALLEL_BELOW_FILLS: u8 = 2;
+
+/// Objective score & payout, each summed across a set of endings. Ordered by score first, with
+/// the payout breaking ties.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Totals {
+    score: u64,
+    payout: u64,
+}
+
+impl Add for Totals {
+    type Output = Totals;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Totals {
+            score: self.score + rhs.score,
+            payout: self.payout + rhs.payout,
+        }
+    }
+}
+
+impl Sum for Totals {
+    #[inline]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Totals::default(), Add::add)
+    }
+}
+
+/// Outcome of revealing each cell of a board, assuming optimal play afterwards.
+///
+/// Every ending of a board is equally likely, so values are kept as the sum of the final payout
+/// across all endings. Dividing by the number of endings gives the expected payout.
+#[derive(Debug, Clone)]
+pub struct Solution {
+    cell_totals: [Option<Totals>; NUM_CELLS as usize],
+    num_endings: u64,
+}
+
+impl Solution {
+    /// Solution for the [expected value objective](Objective::ExpectedValue) from each cell's
+    /// payout total
+    #[cfg(feature = "precomputed")]
+    pub(crate) fn from_payout_totals(
+        totals: [Option<u64>; NUM_CELLS as usize],
+        num_endings: u64,
+    ) -> Self {
+        let mut cell_totals = [None; NUM_CELLS as usize];
+        for (out, total) in cell_totals.iter_mut().zip(totals.iter()) {
+            *out = total.map(|payout| Totals {
+                score: payout,
+                payout,
+            });
+        }
+
+        Self {
+            cell_totals,
+            num_endings,
+        }
+    }
+
+    /// Expected final payout of revealing the given cell; `None` if the cell can't be revealed
+    pub fn expected_value(&self, pos: BoardPosition) -> Option<ExpectedValue> {
+        self.cell_totals
+            .get(pos.index() as usize)
+            .copied()
+            .flatten()
+            .map(|v| ExpectedValue::new(v.payout, self.num_endings))
+    }
+
+    /// Objective score of revealing the given cell; `None` if the cell can't be revealed
+    pub fn score(&self, pos: BoardPosition) -> Option<ExpectedValue> {
+        self.cell_totals
+            .get(pos.index() as usize)
+            .copied()
+            .flatten()
+            .map(|v| ExpectedValue::new(v.score, self.num_endings))
+    }
+
+    /// Cells sharing the highest score
+    pub fn best_cells(&self) -> Vec<BoardPosition> {
+        let max = match self.cell_totals.iter().flatten().max() {
+            Some(v) => *v,
+            None => return Vec::new(),
+        };
+
+        (0..NUM_CELLS)
+            .filter(|idx| self.cell_totals[*idx as usize] == Some(max))
+            .map(BoardPosition::from_index)
+            .collect()
+    }
+}
+
+/// Payout of every line sum & how to score them
+struct SumTotals {
+    payouts: [u16; MAX_SUM as usize + 1],
+    scorer: Scorer,
+}
+
+impl SumTotals {
+    fn new(payouts: &PayoutTable, scorer: &Scorer) -> Self {
+        let mut out = [0; MAX_SUM as usize + 1];
+        for (sum, payout) in out.iter_mut().enumerate() {
+            *payout = payouts.payout(sum as u8);
+        }
+
+        Self {
+            payouts: out,
+            scorer: *scorer,
+        }
+    }
+
+    /// Totals of a line across a set of endings
+    fn line_totals(&self, counts: &SumCounts) -> Totals {
+        let payout_counts = || {
+            counts
+                .iter()
+                .zip(self.payouts.iter())
+                .map(|(count, payout)| (*payout, *count as u64))
+        };
+        let payout = Scorer::Payout.line_total(payout_counts());
+        let score = match self.scorer {
+            Scorer::Payout => payout,
+            scorer => scorer.line_total(payout_counts()),
+        };
+
+        Totals { score, payout }
+    }
+
+    /// Totals of the best line across the endings the stats were collected from
+    fn best_line(&self, stats: &LineStats) -> Totals {
+        stats
+            .sum_counts()
+            .iter()
+            .map(|counts| self.line_totals(counts))
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// Expectimax search over the endings of a board
+struct Solver {
+    memo: HashMap<PackedBoard, Totals>,
+    sum_totals: SumTotals,
+}
+
+impl Solver {
+    fn new(payouts: &PayoutTable, objective: Objective) -> Self {
+        Self {
+            memo: HashMap::new(),
+            sum_totals: SumTotals::new(payouts, &objective.scorer(payouts)),
+        }
+    }
+
+    /// Totals of the given state, playing optimally
+    fn state_total(&mut self, board: PackedBoard, num_fills: u8) -> Totals {
+        if let Some(v) = self.memo.get(&board) {
+            return *v;
+        }
+
+        let out = match num_fills < MAX_REVEALS {
+            true => (0..NUM_CELLS)
+                .filter_map(|idx| self.reveal_total(board, num_fills, idx))
+                .max(),
+            false => None,
+        };
+        let out = out.unwrap_or_else(|| self.sum_totals.best_line(&board.line_stats()));
+        self.memo.insert(board, out);
+
+        out
+    }
+
+    /// Totals of revealing the given cell & playing optimally afterwards.
+    /// `None` if the cell's already been revealed.
+    fn reveal_total(&mut self, board: PackedBoard, num_fills: u8, pos_index: u8) -> Option<Totals> {
+        if board.value_at(pos_index) != 0 {
+            return None;
+        }
+
+        let total = unused_values(board)
+            .map(|value| self.state_total(board.with(pos_index, value), num_fills + 1))
+            .sum();
+
+        Some(total)
+    }
+}
+
+/// Values yet to be revealed on the board
+#[inline]
+fn unused_values(board: PackedBoard) -> impl Iterator<Item = u8> {
+    let used = board.used_values();
+
+    (1..=NUM_CELLS).filter(move |v| used & (1 << v) == 0)
+}
+
+/// Number of equally likely endings of a board with the given number of fills
+#[inline]
+fn num_endings(num_fills: u8) -> u64 {
+    (1..=(NUM_CELLS - num_fills) as u64).product()
+}
+
+fn solve_cells(
+    board: PackedBoard,
+    num_fills: u8,
+    payouts: &PayoutTable,
+    objective: Objective,
+) -> [Option<Totals>; NUM_CELLS as usize] {
+    #[cfg(feature = "parallel")]
+    {
+        if num_fills < PARALLEL_BELOW_FILLS {
+            return parallel_cell_totals(board, num_fills, payouts, objective);
+        }
+    }
+
+    serial_cell_totals(board, num_fills, payouts, objective)
+}
+
+/// Totals of revealing each cell, sharing one memo across all of them
+fn serial_cell_totals(
+    board: PackedBoard,
+    num_fills: u8,
+    payouts: &PayoutTable,
+    objective: Objective,
+) -> [Option<Totals>; NUM_CELLS as usize] {
+    let mut out = [None; NUM_CELLS as usize];
+    let mut solver = Solver::new(payouts, objective);
+
+    for (idx, total) in out.iter_mut().enumerate() {
+        *total = solver.reveal_total(board, num_fills, idx as u8);
+    }
+
+    out
+}
+
+/// Totals of revealing each cell, each cell solved on its own thread with its own memo
+#[cfg(feature = "parallel")]
+fn parallel_cell_totals(
+    board: PackedBoard,
+    num_fills: u8,
+    payouts: &PayoutTable,
+    objective: Objective,
+) -> [Option<Totals>; NUM_CELLS as usize] {
+    use rayon::prelude::*;
+
+    let totals: Vec<Option<Totals>> = (0..NUM_CELLS)
+        .into_par_iter()
+        .map(|idx| Solver::new(payouts, objective).reveal_total(board, num_fills, idx))
+        .collect();
+
+    let mut out = [None; NUM_CELLS as usize];
+    out.copy_from_slice(&totals);
+
+    out
+}
+
+pub fn solve(board: &Board, payouts: &PayoutTable, objective: Objective) -> Solution {
+    let cell_totals = match board.len() < MAX_REVEALS {
+        true => solve_cells(
+            PackedBoard::from_board(board),
+            board.len(),
+            payouts,
+            objective,
+        ),
+        false => [None; NUM_CELLS as usize],
+    };
+
+    Solution {
+        cell_totals,
+        num_endings: num_endings(board.len()),
+    }
+}
+
+/// [`solve`], answering from the [precomputed table](crate::policy_table) when the board's
+/// covered by it
+pub fn lookup_or_solve(board: &Board, payouts: &PayoutTable, objective: Objective) -> Solution {
+    #[cfg(feature = "precomputed")]
+    {
+        let standard = *payouts == PayoutTable::STANDARD;
+        if standard && objective == Objective::ExpectedValue {
+            if let Some(solution) = crate::policy_table::lookup(board) {
+                return solution;
+            }
+        }
+    }
+
+    solve(board, payouts, objective)
+}
+
+/// Like [`solve`], but assumes the line gets picked straight after the reveal
+pub fn solve_myopic(board: &Board, payouts: &PayoutTable, objective: Objective) -> Solution {
+    let mut cell_totals = [None; NUM_CELLS as usize];
+
+    if board.len() < MAX_REVEALS {
+        let sum_totals = SumTotals::new(payouts, &objective.scorer(payouts));
+        let packed = PackedBoard::from_board(board);
+
+        for (idx, total) in cell_totals.iter_mut().enumerate() {
+            let idx = idx as u8;
+            if packed.value_at(idx) != 0 {
+                continue;
+            }
+
+            let sum = unused_values(packed)
+                .map(|value| sum_totals.best_line(&packed.with(idx, value).line_stats()))
+                .sum();
+            *total = Some(sum);
+        }
+    }
+
+    Solution {
+        cell_totals,
+        num_endings: num_endings(board.len()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parsed_board::ParsedBoard;
+    use crate::ValuedBoardPosition;
+
+    /// Totals of the best line, going through every ending the long way
+    fn best_line_total_of(board: &Board, objective: Objective) -> Totals {
+        let payouts = PayoutTable::STANDARD;
+        let scorer = objective.scorer(&payouts);
+        let mut line_payouts: Vec<Vec<(u16, u64)>> = vec![Vec::new(); 8];
+        PackedBoard::from_board(board).for_each_ending(|cells| {
+            let mut ending = Board::default();
+            for (idx, value) in cells.iter().enumerate() {
+                let pos = BoardPosition::from_index(idx as u8);
+                ending
+                    .fill(ValuedBoardPosition::from_pos(*value, pos))
+                    .unwrap();
+            }
+            let parsed = ParsedBoard::from_board(ending, &payouts);
+
+            for (line, payout) in line_payouts.iter_mut().zip(parsed.line_payouts().iter()) {
+                line.push((*payout, 1));
+            }
+        });
+
+        line_payouts
+            .into_iter()
+            .map(|payouts| Totals {
+                score: scorer.line_total(payouts.iter().copied()),
+                payout: Scorer::Payout.line_total(payouts),
+            })
+            .max()
+            .unwrap()
+    }
+
+    fn solve_std(board: &Board, objective: Objective) -> Solution {
+        solve(board, &PayoutTable::STANDARD, objective)
+    }
+
+    #[test]
+    fn last_scratch_matches_brute_force() {
+        let board = "1_9/_5_/___".parse::<Board>().unwrap();
+        let objectives = [
+            Objective::ExpectedValue,
+            Objective::Jackpot,
+            Objective::AtLeast(720),
+            Objective::MinVariance,
+        ];
+
+        for objective in objectives.iter() {
+            let solution = solve_std(&board, *objective);
+
+            for idx in 0..NUM_CELLS {
+                let pos = BoardPosition::from_index(idx);
+                if board.contains_position(pos) {
+                    assert_eq!(solution.cell_totals[idx as usize], None, "{}", pos);
+                    continue;
+                }
+
+                let exp: Totals = board
+                    .available_selections()
+                    .map(|value| {
+                        let mut board = board.clone();
+                        board
+                            .fill(ValuedBoardPosition::from_pos(value, pos))
+                            .unwrap();
+                        best_line_total_of(&board, *objective)
+                    })
+                    .sum();
+
+                assert_eq!(
+                    solution.cell_totals[idx as usize],
+                    Some(exp),
+                    "{} {}",
+                    objective,
+                    pos
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn best_cells() {
+        let board = "2__/_4_/___".parse::<Board>().unwrap();
+        let solution = solve_std(&board, Objective::ExpectedValue);
+        let best = solution.best_cells();
+        let max = best
+            .iter()
+            .map(|p| solution.cell_totals[p.index() as usize].unwrap())
+            .max();
+
+        assert!(!best.is_empty());
+        for idx in 0..NUM_CELLS {
+            let pos = BoardPosition::from_index(idx);
+            if let Some(total) = solution.cell_totals[pos.index() as usize] {
+                assert!(Some(total) <= max, "{}", pos);
+                assert_eq!(best.contains(&pos), Some(total) == max, "{}", pos);
+            }
+        }
+    }
+
+    #[test]
+    fn nothing_to_reveal_once_full() {
+        let board = "123/4__/___".parse::<Board>().unwrap();
+        let solution = solve_std(&board, Objective::ExpectedValue);
+
+        assert!(solution.best_cells().is_empty());
+        assert!(solution.cell_totals.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn expected_value() {
+        let board = "_8_/7__/__3".parse::<Board>().unwrap();
+        let solution = solve_std(&board, Objective::ExpectedValue);
+
+        assert_eq!(solution.num_endings, 720);
+        for idx in 0..NUM_CELLS {
+            let pos = BoardPosition::from_index(idx);
+            let exp = solution.cell_totals[idx as usize].map(|v| ExpectedValue::new(v.payout, 720));
+
+            assert_eq!(solution.expected_value(pos), exp, "{}", pos);
+            assert_eq!(solution.score(pos), exp, "{}", pos);
+        }
+    }
+
+    #[test]
+    fn jackpot() {
+        // 1 & 2 leave a 3 up for grabs on the diagonal
+        let board = "1__/_2_/___".parse::<Board>().unwrap();
+        let solution = solve_std(&board, Objective::Jackpot);
+
+        for pos in solution.best_cells() {
+            let chance = solution.score(pos).unwrap();
+
+            assert!(chance > ExpectedValue::new(0, 1), "{}", pos);
+            assert!(chance <= ExpectedValue::new(1, 1), "{}", pos);
+        }
+    }
+
+    #[test]
+    fn myopic_last_scratch() {
+        let board = "1_9/_5_/___".parse::<Board>().unwrap();
+        let payouts = PayoutTable::STANDARD;
+        let full = solve(&board, &payouts, Objective::ExpectedValue);
+        let myopic = solve_myopic(&board, &payouts, Objective::ExpectedValue);
+
+        assert_eq!(full.cell_totals, myopic.cell_totals);
+    }
+
+    #[test]
+    fn myopic_never_beats_full() {
+        let board = "___/___/6__".parse::<Board>().unwrap();
+        let payouts = PayoutTable::STANDARD;
+        let full = solve(&board, &payouts, Objective::ExpectedValue);
+        let myopic = solve_myopic(&board, &payouts, Objective::ExpectedValue);
+
+        for (a, b) in full.cell_totals.iter().zip(myopic.cell_totals.iter()) {
+            assert!(b <= a);
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_serial() {
+        let boards = [
+            "___/_5_/___".parse::<Board>().unwrap(),
+            "___/___/2__".parse::<Board>().unwrap(),
+            "___/__7/3__".parse::<Board>().unwrap(),
+            "1__/_6_/___".parse::<Board>().unwrap(),
+        ];
+        let payouts = PayoutTable::STANDARD.with_bonus(10);
+
+        for board in boards.iter() {
+            for objective in [Objective::ExpectedValue, Objective::AtLeast(1800)].iter() {
+                let packed = PackedBoard::from_board(board);
+                let parallel = parallel_cell_totals(packed, board.len(), &payouts, *objective);
+                let serial = serial_cell_totals(packed, board.len(), &payouts, *objective);
+
+                assert_eq!(parallel, serial, "{:?} {}", board, objective);
+            }
+        }
+    }
+}
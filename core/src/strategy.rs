@@ -0,0 +1,111 @@
+pub use greedy::GreedyStrategy;
+pub use heuristic::HeuristicStrategy;
+pub use optimal::OptimalStrategy;
+pub use random::RandomStrategy;
+
+use super::solver::MAX_REVEALS;
+use super::{Board, BoardPosition, Line};
+
+mod greedy;
+mod heuristic;
+mod optimal;
+mod random;
+
+/// A policy for playing a board: which cell to scratch next and which line to pick at the end
+pub trait Strategy {
+    /// Cell to scratch next. `None` on an empty board, as the first cell is chosen by the game,
+    /// and once all the scratches have been used up.
+    fn choose_reveal(&mut self, board: &Board) -> Option<BoardPosition>;
+
+    /// Line to pick on the current board. `None` on an empty board.
+    fn choose_line(&mut self, board: &Board) -> Option<Line>;
+}
+
+impl<S: Strategy + ?Sized> Strategy for Box<S> {
+    #[inline]
+    fn choose_reveal(&mut self, board: &Board) -> Option<BoardPosition> {
+        (**self).choose_reveal(board)
+    }
+
+    #[inline]
+    fn choose_line(&mut self, board: &Board) -> Option<Line> {
+        (**self).choose_line(board)
+    }
+}
+
+/// Whether there's a cell left for the player to scratch
+#[inline]
+fn can_reveal(board: &Board) -> bool {
+    !board.is_empty() && board.len() < MAX_REVEALS
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{PayoutTable, Recommendation};
+
+    fn all_strategies() -> Vec<Box<dyn Strategy>> {
+        vec![
+            Box::new(HeuristicStrategy::default()),
+            Box::new(OptimalStrategy::default()),
+            Box::new(GreedyStrategy::default()),
+            Box::new(RandomStrategy::seeded(7)),
+        ]
+    }
+
+    #[test]
+    fn nothing_to_do_on_empty_board() {
+        let board = Board::default();
+
+        for mut strategy in all_strategies() {
+            assert_eq!(strategy.choose_reveal(&board), None);
+            assert_eq!(strategy.choose_line(&board), None);
+        }
+    }
+
+    #[test]
+    fn reveals_unfilled_cells() {
+        let board = "2__/_4_/___".parse::<Board>().unwrap();
+
+        for mut strategy in all_strategies() {
+            let pos = strategy.choose_reveal(&board).unwrap();
+
+            assert!(!board.contains_position(pos), "{}", pos);
+            assert!(strategy.choose_line(&board).is_some());
+        }
+    }
+
+    #[test]
+    fn no_reveals_once_full() {
+        let board = "123/4__/___".parse::<Board>().unwrap();
+
+        for mut strategy in all_strategies() {
+            assert_eq!(strategy.choose_reveal(&board), None);
+            assert!(strategy.choose_line(&board).is_some());
+        }
+    }
+
+    #[test]
+    fn matches_recommendation() {
+        let board = "_8_/7__/__3".parse::<Board>().unwrap();
+        let rec = Recommendation::from_board(&board).unwrap();
+
+        assert_eq!(
+            HeuristicStrategy::default().choose_reveal(&board),
+            rec.heuristic_suggestions().first().copied()
+        );
+        assert_eq!(
+            OptimalStrategy::default().choose_reveal(&board),
+            rec.suggestions().first().copied()
+        );
+        assert_eq!(
+            OptimalStrategy::default().choose_line(&board),
+            rec.best_lines().first().copied()
+        );
+        // Only one scratch left, so looking one step ahead is as good as it gets
+        assert_eq!(
+            GreedyStrategy::new(PayoutTable::STANDARD).choose_reveal(&board),
+            rec.suggestions().first().copied()
+        );
+    }
+}
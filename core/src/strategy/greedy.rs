@@ -0,0 +1,44 @@
+use super::{can_reveal, Strategy};
+use crate::line::best_lines;
+use crate::packed_board::PackedBoard;
+use crate::solver;
+use crate::{Board, BoardPosition, Line, Objective, PayoutTable};
+
+/// Scratches whichever cell would be best if the line had to be picked straight afterwards,
+/// ignoring any scratches left after it
+#[derive(Debug, Copy, Clone, Default)]
+pub struct GreedyStrategy {
+    payouts: PayoutTable,
+}
+
+impl GreedyStrategy {
+    #[inline]
+    pub fn new(payouts: PayoutTable) -> Self {
+        Self { payouts }
+    }
+}
+
+impl Strategy for GreedyStrategy {
+    fn choose_reveal(&mut self, board: &Board) -> Option<BoardPosition> {
+        if !can_reveal(board) {
+            return None;
+        }
+
+        solver::solve_myopic(board, &self.payouts, Objective::ExpectedValue)
+            .best_cells()
+            .first()
+            .copied()
+    }
+
+    fn choose_line(&mut self, board: &Board) -> Option<Line> {
+        if board.is_empty() {
+            return None;
+        }
+
+        let averages = PackedBoard::from_board(board)
+            .line_stats()
+            .averages(&self.payouts);
+
+        best_lines(&averages, &averages).first().copied()
+    }
+}
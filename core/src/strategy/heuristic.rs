@@ -0,0 +1,47 @@
+use super::{can_reveal, Strategy};
+use crate::line::best_lines;
+use crate::packed_board::PackedBoard;
+use crate::{Board, BoardPosition, Line, PayoutTable, Recommendation};
+
+/// Scratches the cells touching the most lines tied for the highest average & picks the line
+/// with the highest average
+#[derive(Debug, Copy, Clone, Default)]
+pub struct HeuristicStrategy {
+    payouts: PayoutTable,
+}
+
+impl HeuristicStrategy {
+    #[inline]
+    pub fn new(payouts: PayoutTable) -> Self {
+        Self { payouts }
+    }
+}
+
+impl Strategy for HeuristicStrategy {
+    fn choose_reveal(&mut self, board: &Board) -> Option<BoardPosition> {
+        if !can_reveal(board) {
+            return None;
+        }
+
+        let averages = PackedBoard::from_board(board)
+            .line_stats()
+            .averages(&self.payouts);
+        let max_avg = Recommendation::calc_max_avg(&averages, None)?;
+
+        Recommendation::mk_suggestions(board, &averages, max_avg)
+            .first()
+            .copied()
+    }
+
+    fn choose_line(&mut self, board: &Board) -> Option<Line> {
+        if board.is_empty() {
+            return None;
+        }
+
+        let averages = PackedBoard::from_board(board)
+            .line_stats()
+            .averages(&self.payouts);
+
+        best_lines(&averages, &averages).first().copied()
+    }
+}
@@ -0,0 +1,46 @@
+use super::{can_reveal, Strategy};
+use crate::line::best_lines;
+use crate::packed_board::PackedBoard;
+use crate::solver;
+use crate::{Board, BoardPosition, Line, Objective, PayoutTable};
+
+/// Plays optimally for the given [objective](Objective), looking ahead through every
+/// remaining scratch
+#[derive(Debug, Copy, Clone, Default)]
+pub struct OptimalStrategy {
+    payouts: PayoutTable,
+    objective: Objective,
+}
+
+impl OptimalStrategy {
+    #[inline]
+    pub fn new(payouts: PayoutTable, objective: Objective) -> Self {
+        Self { payouts, objective }
+    }
+}
+
+impl Strategy for OptimalStrategy {
+    fn choose_reveal(&mut self, board: &Board) -> Option<BoardPosition> {
+        if !can_reveal(board) {
+            return None;
+        }
+
+        solver::lookup_or_solve(board, &self.payouts, self.objective)
+            .best_cells()
+            .first()
+            .copied()
+    }
+
+    fn choose_line(&mut self, board: &Board) -> Option<Line> {
+        if board.is_empty() {
+            return None;
+        }
+
+        let stats = PackedBoard::from_board(board).line_stats();
+        let scores = stats.scores(&self.payouts, &self.objective.scorer(&self.payouts));
+
+        best_lines(&scores, &stats.averages(&self.payouts))
+            .first()
+            .copied()
+    }
+}
@@ -0,0 +1,74 @@
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+use rand::{Rng, SeedableRng};
+
+use super::{can_reveal, Strategy};
+use crate::{Board, BoardPosition, Line};
+
+/// Scratches a random unrevealed cell & picks a random line. Useful as a baseline.
+#[derive(Debug, Clone)]
+pub struct RandomStrategy<R = StdRng> {
+    rng: R,
+}
+
+impl<R: Rng> RandomStrategy<R> {
+    #[inline]
+    pub fn new(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+impl RandomStrategy<StdRng> {
+    /// Strategy making the same choices every time for the same seed
+    #[inline]
+    pub fn seeded(seed: u64) -> Self {
+        Self::new(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for RandomStrategy<StdRng> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(StdRng::from_entropy())
+    }
+}
+
+impl<R: Rng> Strategy for RandomStrategy<R> {
+    fn choose_reveal(&mut self, board: &Board) -> Option<BoardPosition> {
+        if !can_reveal(board) {
+            return None;
+        }
+
+        (0..9)
+            .map(BoardPosition::from_index)
+            .filter(|pos| !board.contains_position(*pos))
+            .choose(&mut self.rng)
+    }
+
+    fn choose_line(&mut self, board: &Board) -> Option<Line> {
+        if board.is_empty() {
+            return None;
+        }
+
+        Line::ALL.iter().copied().choose(&mut self.rng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ValuedBoardPosition;
+
+    #[test]
+    fn same_seed_same_choices() {
+        let mut board = Board::default();
+        board.fill(ValuedBoardPosition::from_u8(5, 1, 1)).unwrap();
+
+        let mut a = RandomStrategy::seeded(42);
+        let mut b = RandomStrategy::seeded(42);
+        for _ in 0..20 {
+            assert_eq!(a.choose_reveal(&board), b.choose_reveal(&board));
+            assert_eq!(a.choose_line(&board), b.choose_line(&board));
+        }
+    }
+}
@@ -1,9 +1,12 @@
-use eframe::egui::{CentralPanel, CtxRef, Direction, Label, Layout, Ui, Vec2};
+use eframe::egui::{Button, CentralPanel, CtxRef, Direction, Label, Layout, Slider, Ui, Vec2};
 use eframe::epi::{App, Frame, IconData};
 
+use cactpot_solver_core::{Objective, Phase};
+
 use crate::app::state::CactpotState;
 
-pub(crate) const WINDOW_SIZE: Vec2 = Vec2::new(440.0, 350.0);
+pub(crate) const WINDOW_SIZE: Vec2 = Vec2::new(440.0, 433.0);
+const MAX_BONUS: u16 = 100;
 
 pub(crate) mod grid;
 pub(crate) mod grid_btn;
@@ -12,26 +15,91 @@ pub(crate) mod state;
 
 pub struct CactpotSolverGUI {
     state: CactpotState,
+    /// Bonus shown on the slider, which only gets applied once the slider's let go of
+    bonus: u16,
 }
 
 impl CactpotSolverGUI {
     fn draw_suggestions(&self, ui: &mut Ui) {
-        let txt = match self.state.board().len() {
-            0 => "Select the number the game's chosen for you",
-            4 => "Good luck!",
-            _ => "Pick the next number",
+        let txt = match self.state.game().phase() {
+            Phase::InitialReveal => "Select the number the game's chosen for you".to_owned(),
+            Phase::Scratching { remaining } => format!("Pick the next number ({} left)", remaining),
+            Phase::LineSelection => "Pick a highlighted line. Good luck!".to_owned(),
+            Phase::Resolved { payout, .. } => format!("Won {} MGP", payout),
         };
         ui.add_sized(Vec2::new(WINDOW_SIZE.x, 14.0), Label::new(txt));
+
+        if let Some(rec) = self.state.recommendation() {
+            let line = rec.best_lines()[0];
+            let txt = match rec.objective() {
+                Objective::ExpectedValue => format!("Best line: {} MGP", rec.line_value(line)),
+                Objective::MinVariance => format!(
+                    "Best line: {} MGP expected, give or take {:.0}",
+                    rec.line_value(line),
+                    rec.distribution(line).std_dev()
+                ),
+                _ => format!(
+                    "Best line: {:.1}% chance, {} MGP expected",
+                    rec.line_score(line).as_f64() * 100.0,
+                    rec.line_value(line)
+                ),
+            };
+            ui.add_sized(Vec2::new(WINDOW_SIZE.x, 14.0), Label::new(txt));
+        }
+
+        if let Some(value) = self.state.game_value() {
+            let txt = format!(
+                "Ticket worth: {} MGP playing optimally, {} MGP by the heuristic",
+                value.optimal(),
+                value.heuristic()
+            );
+            ui.add_sized(Vec2::new(WINDOW_SIZE.x, 14.0), Label::new(txt));
+        }
     }
 
     fn draw_controls(&mut self, ui: &mut Ui) {
+        // Every new bonus means solving live, which is too slow to keep up with a drag
+        let slider = Slider::new(&mut self.bonus, 0..=MAX_BONUS).text("MGP bonus %");
+        let response = ui.add(slider);
+        let settled = response.drag_released()
+            || response.lost_focus()
+            || (response.changed() && !response.dragged());
+        if settled && self.bonus != self.state.bonus() {
+            self.state.set_bonus(self.bonus);
+        }
+
+        let mut objective = self.state.objective();
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut objective, Objective::ExpectedValue, "Max expected MGP");
+            ui.radio_value(&mut objective, Objective::Jackpot, "Max jackpot chance");
+            ui.radio_value(&mut objective, Objective::MinVariance, "Min variance");
+        });
+        if objective != self.state.objective() {
+            self.state.set_objective(objective);
+        }
+
         ui.with_layout(
             Layout::centered_and_justified(Direction::LeftToRight),
             |ui| {
-                if ui.button("Reset").clicked() {
-                    self.state.board_mut().clear_fills();
-                    self.state.clear_recommendation();
-                }
+                ui.horizontal(|ui| {
+                    let undo = Button::new("Undo").enabled(self.state.can_undo());
+                    if ui.add(undo).clicked() {
+                        if let Err(e) = self.state.undo() {
+                            eprintln!("Failed to undo: {}", e);
+                        }
+                    }
+
+                    let redo = Button::new("Redo").enabled(self.state.can_redo());
+                    if ui.add(redo).clicked() {
+                        if let Err(e) = self.state.redo() {
+                            eprintln!("Failed to redo: {}", e);
+                        }
+                    }
+
+                    if ui.button("Reset").clicked() {
+                        self.state.reset();
+                    }
+                });
             },
         );
     }
@@ -83,8 +151,9 @@ impl App for CactpotSolverGUI {
 
 impl Default for CactpotSolverGUI {
     fn default() -> Self {
-        Self {
-            state: CactpotState::default(),
-        }
+        let state = CactpotState::default();
+        let bonus = state.bonus();
+
+        Self { state, bonus }
     }
 }
@@ -1,6 +1,6 @@
 use eframe::egui::{self, Align, Direction, Label, Layout, Sense, Ui, Vec2};
 
-use cactpot_solver_core::BoardPosition;
+use cactpot_solver_core::{BoardPosition, Line};
 
 use crate::app::grid_cell;
 use crate::app::state::CactpotState;
@@ -10,7 +10,7 @@ const SPACING: Vec2 = Vec2::new(10.0, 10.0);
 mod print_tip {
     use eframe::egui::{Color32, Label};
 
-    use cactpot_solver_core::Recommendation;
+    use cactpot_solver_core::{Line, Recommendation};
 
     use super::{grid_cell, Align, CactpotState, Direction, Layout, Sense, Ui, Vec2};
 
@@ -20,41 +20,37 @@ mod print_tip {
     const SUGGESTION_BEST_COLOUR: Color32 = Color32::from_rgb(9, 209, 2);
 
     #[inline]
-    fn resolve_colour<T: PartialEq<T>>(tested_value: T, avg_value: T) -> Color32 {
-        if avg_value == tested_value {
+    fn resolve_colour(recommendation: &Recommendation, line: Line) -> Color32 {
+        if recommendation.best_lines().contains(&line) {
             SUGGESTION_BEST_COLOUR
         } else {
             SUGGESTION_COLOUR
         }
     }
 
-    pub fn row(ui: &mut Ui, row: usize, state: &CactpotState) {
+    fn mk_label(recommendation: &Recommendation, line: Line) -> Label {
+        Label::new(recommendation.line_value(line).to_string())
+            .text_color(resolve_colour(recommendation, line))
+    }
+
+    pub fn row(ui: &mut Ui, row: u8, state: &CactpotState) {
         let (rect, _) = ui.allocate_exact_size(TIP_SIZE_ROW, Sense::hover());
 
         ui.allocate_ui_at_rect(rect, |ui| {
             if let Some(recommendation) = state.recommendation() {
-                let lbl = {
-                    let avg = recommendation.avg_row()[row];
-                    Label::new(avg.to_string())
-                        .text_color(resolve_colour(avg, recommendation.max_avg()))
-                };
-                ui.add(lbl);
+                ui.add(mk_label(recommendation, Line::row(row)));
             }
         });
     }
 
-    pub fn col(ui: &mut Ui, col: usize, recommendation: &Recommendation) {
-        let lbl = {
-            let avg = recommendation.avg_col()[col];
-            Label::new(avg.to_string()).text_color(resolve_colour(avg, recommendation.max_avg()))
-        };
-        ui.add_sized(TIP_SIZE_COL, lbl);
+    pub fn col(ui: &mut Ui, col: u8, recommendation: &Recommendation) {
+        ui.add_sized(TIP_SIZE_COL, mk_label(recommendation, Line::col(col)));
     }
 
-    pub fn diag(ui: &mut Ui, avg: u16, max_avg: u16, dir: Direction) {
+    pub fn diag(ui: &mut Ui, recommendation: &Recommendation, line: Line, dir: Direction) {
         let layout = Layout::from_main_dir_and_cross_align(dir, Align::Center);
         ui.allocate_ui_with_layout(TIP_SIZE_COL, layout, |ui| {
-            ui.add(Label::new(avg.to_string()).text_color(resolve_colour(avg, max_avg)));
+            ui.add(mk_label(recommendation, line));
         });
     }
 }
@@ -66,7 +62,7 @@ fn draw_main(ui: &mut Ui, state: &mut CactpotState) {
             for col in 0u8..3 {
                 grid_cell::draw(ui, state, BoardPosition::new(col, row));
             }
-            print_tip::row(ui, row as usize, state);
+            print_tip::row(ui, row, state);
         });
     }
 }
@@ -79,17 +75,17 @@ fn draw_bottom(ui: &mut Ui, state: &CactpotState) {
             Some(recommendation) => {
                 print_tip::diag(
                     ui,
-                    recommendation.avg_bl_tr(),
-                    recommendation.max_avg(),
+                    recommendation,
+                    Line::BottomLeftTopRight,
                     Direction::RightToLeft,
                 );
-                for col in 0..3 {
+                for col in 0u8..3 {
                     print_tip::col(ui, col, recommendation);
                 }
                 print_tip::diag(
                     ui,
-                    recommendation.avg_tl_br(),
-                    recommendation.max_avg(),
+                    recommendation,
+                    Line::TopLeftBottomRight,
                     Direction::LeftToRight,
                 );
             }
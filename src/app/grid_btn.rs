@@ -6,6 +6,7 @@ use crate::app::state::CactpotState;
 
 const SIZE: Vec2 = Vec2::new(25.0, 25.0);
 const SUGGESTED_COLOUR: Color32 = Color32::from_rgb(2, 125, 232);
+const CURRENT_COLOUR: Color32 = Color32::from_rgb(9, 209, 2);
 
 fn resolve_sense(is_enabled: bool) -> Sense {
     match is_enabled {
@@ -14,30 +15,79 @@ fn resolve_sense(is_enabled: bool) -> Sense {
     }
 }
 
-fn calc_is_enabled(board: &Board, pos: &ValuedBoardPosition) -> bool {
-    board.len() < 4 && !board.contains_value(pos.value())
+fn calc_is_enabled(state: &CactpotState, pos: &ValuedBoardPosition) -> bool {
+    state.game().can_reveal() && !state.board().contains_value(pos.value())
 }
 
-pub fn draw(
+fn calc_is_enabled_edit(board: &Board, pos: &ValuedBoardPosition, current: u8) -> bool {
+    pos.value() == current || !board.contains_value(pos.value())
+}
+
+fn draw_btn(
     ui: &mut Ui,
-    state: &mut CactpotState,
-    pos: ValuedBoardPosition,
-    is_suggested: bool,
+    pos: &ValuedBoardPosition,
+    is_enabled: bool,
+    fill: Option<Color32>,
 ) -> egui::Response {
-    let is_enabled = calc_is_enabled(state.board(), &pos);
     let (rect, rsp) = ui.allocate_exact_size(SIZE, resolve_sense(is_enabled));
 
     let mut btn = Button::new(pos.value().to_string());
-    if is_suggested && is_enabled {
-        btn = btn.fill(Some(SUGGESTED_COLOUR))
+    if is_enabled {
+        btn = btn.fill(fill)
     }
     ui.put(rect, btn.enabled(is_enabled));
 
+    rsp
+}
+
+/// Button correcting a revealed cell: the current value clears it, any other changes it & a
+/// right click leaves the cell as it is
+pub fn draw_edit(
+    ui: &mut Ui,
+    state: &mut CactpotState,
+    pos: ValuedBoardPosition,
+    current: u8,
+) -> egui::Response {
+    let is_current = pos.value() == current;
+    let is_enabled = calc_is_enabled_edit(state.board(), &pos, current);
+    let fill = match is_current {
+        true => Some(CURRENT_COLOUR),
+        false => None,
+    };
+    let rsp = draw_btn(ui, &pos, is_enabled, fill);
+
+    if rsp.clicked() {
+        let result = match is_current {
+            true => state.clear(pos.position()),
+            false => state.change(pos),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to correct: {}", e);
+        }
+    } else if rsp.secondary_clicked() {
+        state.set_editing(None);
+    }
+
+    rsp
+}
+
+pub fn draw(
+    ui: &mut Ui,
+    state: &mut CactpotState,
+    pos: ValuedBoardPosition,
+    is_suggested: bool,
+) -> egui::Response {
+    let is_enabled = calc_is_enabled(state, &pos);
+    let fill = match is_suggested {
+        true => Some(SUGGESTED_COLOUR),
+        false => None,
+    };
+    let rsp = draw_btn(ui, &pos, is_enabled, fill);
+
     if rsp.clicked() {
-        if let Err(e) = state.board_mut().fill(pos) {
+        if let Err(e) = state.fill(pos) {
             eprintln!("Failed to fill: {}", e);
         }
-        state.update_recommendation();
     }
 
     rsp
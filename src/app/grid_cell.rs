@@ -1,6 +1,6 @@
-use eframe::egui::{self, Direction, Layout, Ui, Vec2};
+use eframe::egui::{self, Direction, Key, Layout, Sense, Ui, Vec2};
 
-use cactpot_solver_core::{Board, BoardPosition, ValuedBoardPosition};
+use cactpot_solver_core::{BoardPosition, RevealOutcome, RevealPreview, ValuedBoardPosition};
 
 use crate::app::state::CactpotState;
 
@@ -9,26 +9,16 @@ use super::grid_btn;
 pub const SIZE: Vec2 = Vec2::new(79.0, 79.0);
 const SPACING: Vec2 = Vec2::new(2.0, 2.0);
 
-fn draw_filled(target_ui: &mut Ui, board: &Board, pos: BoardPosition) -> egui::Response {
-    target_ui
-        .allocate_ui_with_layout(
-            SIZE,
-            Layout::centered_and_justified(Direction::LeftToRight),
-            |ui| {
-                if let Some(v) = board.find(pos) {
-                    ui.heading(v.value().to_string());
-                }
-            },
-        )
-        .response
-}
-
-fn draw_unfilled(ui: &mut Ui, state: &mut CactpotState, cell_pos: BoardPosition) -> egui::Response {
-    let is_suggested = match state.recommendation() {
-        Some(v) => v.suggestions().contains(&cell_pos),
-        None => false,
-    };
-
+/// A button for each value 1-9 laid out in a 3x3 grid
+fn draw_value_grid<F>(
+    ui: &mut Ui,
+    state: &mut CactpotState,
+    cell_pos: BoardPosition,
+    mut draw_btn: F,
+) -> egui::Response
+where
+    F: FnMut(&mut Ui, &mut CactpotState, ValuedBoardPosition),
+{
     ui.allocate_ui(SIZE, |ui| {
         ui.vertical(|ui| {
             ui.spacing_mut().item_spacing = SPACING;
@@ -41,7 +31,7 @@ fn draw_unfilled(ui: &mut Ui, state: &mut CactpotState, cell_pos: BoardPosition)
 
                     for col in 0u8..3 {
                         let vbs = ValuedBoardPosition::from_pos(value + col, cell_pos);
-                        grid_btn::draw(ui, state, vbs, is_suggested);
+                        draw_btn(ui, state, vbs);
                     }
                 });
             }
@@ -50,9 +40,102 @@ fn draw_unfilled(ui: &mut Ui, state: &mut CactpotState, cell_pos: BoardPosition)
     .response
 }
 
+fn draw_filled(target_ui: &mut Ui, state: &mut CactpotState, pos: BoardPosition) -> egui::Response {
+    let value = match state.board().find(pos) {
+        Some(v) => v.value(),
+        None => 0,
+    };
+
+    if state.editing() == Some(pos) {
+        if target_ui.input().key_pressed(Key::Escape) {
+            state.set_editing(None);
+        }
+
+        return draw_value_grid(target_ui, state, pos, |ui, state, vbs| {
+            grid_btn::draw_edit(ui, state, vbs, value);
+        })
+        .on_hover_text(
+            "Click the current value to clear the cell or another one to change it. \
+             Right click or press Esc to leave it as it is.",
+        );
+    }
+
+    let rsp = target_ui
+        .allocate_ui_with_layout(
+            SIZE,
+            Layout::centered_and_justified(Direction::LeftToRight),
+            |ui| {
+                ui.heading(value.to_string());
+            },
+        )
+        .response;
+    let id = target_ui.make_persistent_id(("filled", pos.index()));
+    let rsp = target_ui
+        .interact(rsp.rect, id, Sense::click())
+        .on_hover_text("Click to clear or change");
+
+    if rsp.clicked() {
+        state.set_editing(Some(pos));
+    }
+
+    rsp
+}
+
+fn describe_outcome(outcome: &RevealOutcome) -> String {
+    let rec = outcome.recommendation();
+    let best_line = match rec.best_lines().first() {
+        Some(line) => format!("{} ({} MGP)", line, rec.line_value(*line)),
+        None => String::new(),
+    };
+    let next = match outcome.suggestions().is_empty() {
+        true => "pick a line".to_owned(),
+        false => outcome
+            .suggestions()
+            .iter()
+            .map(|pos| format!("({})", pos))
+            .collect::<Vec<_>>()
+            .join(" "),
+    };
+
+    format!(
+        "{} ({:.0}%): best {}, next {}",
+        outcome.value(),
+        outcome.probability() * 100.0,
+        best_line,
+        next
+    )
+}
+
+fn draw_preview(ui: &mut Ui, preview: &RevealPreview) {
+    ui.label(format!("If ({}) reveals...", preview.position()));
+    for outcome in preview.outcomes() {
+        ui.label(describe_outcome(outcome));
+    }
+}
+
+fn draw_unfilled(ui: &mut Ui, state: &mut CactpotState, cell_pos: BoardPosition) -> egui::Response {
+    let is_suggested = match state.recommendation() {
+        Some(v) => v.suggestions().contains(&cell_pos),
+        None => false,
+    };
+
+    let rsp = draw_value_grid(ui, state, cell_pos, |ui, state, vbs| {
+        grid_btn::draw(ui, state, vbs, is_suggested);
+    });
+
+    // Only worked out once the cell's actually hovered
+    if !rsp.hovered() {
+        return rsp;
+    }
+    match state.preview(cell_pos) {
+        Some(preview) => rsp.on_hover_ui(|ui| draw_preview(ui, preview)),
+        None => rsp,
+    }
+}
+
 pub fn draw(ui: &mut Ui, state: &mut CactpotState, cell_pos: BoardPosition) -> egui::Response {
     if state.board().contains_position(cell_pos) {
-        draw_filled(ui, state.board(), cell_pos)
+        draw_filled(ui, state, cell_pos)
     } else {
         draw_unfilled(ui, state, cell_pos)
     }
@@ -1,33 +1,153 @@
-use cactpot_solver_core::{Board, Recommendation};
+use cactpot_solver_core::{
+    Board, BoardPosition, Game, GameError, GameValue, Objective, Recommendation, RevealPreview,
+    Session, ValuedBoardPosition,
+};
 
 #[derive(Default)]
 pub struct CactpotState {
-    board: Board,
-    recommendation: Option<Recommendation>,
+    /// Enforces the rules & works out the recommendations
+    session: Session,
+    /// What-if previews of each cell, worked out the first time the cell's hovered
+    previews: [Option<RevealPreview>; 9],
+    game_value: Option<GameValue>,
+    /// Revealed cell being corrected
+    editing: Option<BoardPosition>,
 }
 
 impl CactpotState {
     #[inline]
     pub fn board(&self) -> &Board {
-        &self.board
+        self.session.board()
     }
 
     #[inline]
-    pub fn board_mut(&mut self) -> &mut Board {
-        &mut self.board
+    pub fn game(&self) -> &Game {
+        self.session.game()
     }
 
     #[inline]
-    pub fn recommendation(&self) -> &Option<Recommendation> {
-        &self.recommendation
+    pub fn recommendation(&self) -> Option<&Recommendation> {
+        self.session.recommendation().ok()
     }
 
+    /// What the ticket's worth from the current board
     #[inline]
-    pub fn clear_recommendation(&mut self) {
-        self.recommendation = None;
+    pub fn game_value(&self) -> Option<&GameValue> {
+        self.game_value.as_ref()
     }
 
-    pub fn update_recommendation(&mut self) {
-        self.recommendation = Recommendation::from_board(&self.board).ok();
+    pub fn preview(&mut self, pos: BoardPosition) -> Option<&RevealPreview> {
+        let session = &self.session;
+        let preview = &mut self.previews[pos.index() as usize];
+        if preview.is_none() {
+            *preview = RevealPreview::from_board_for(
+                session.board(),
+                pos,
+                session.payouts(),
+                session.objective(),
+            )
+            .ok();
+        }
+
+        preview.as_ref()
+    }
+
+    pub fn fill(&mut self, pos: ValuedBoardPosition) -> Result<(), GameError> {
+        self.session.fill(pos)?;
+        self.invalidate();
+
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        self.session.reset();
+        self.invalidate();
+    }
+
+    /// Clear a revealed cell
+    pub fn clear(&mut self, pos: BoardPosition) -> Result<(), GameError> {
+        self.session.remove(pos)?;
+        self.invalidate();
+
+        Ok(())
+    }
+
+    /// Correct the value of a revealed cell
+    pub fn change(&mut self, pos: ValuedBoardPosition) -> Result<(), GameError> {
+        self.session.replace(pos)?;
+        self.invalidate();
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        self.session.can_undo()
+    }
+
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        self.session.can_redo()
+    }
+
+    pub fn undo(&mut self) -> Result<(), GameError> {
+        if self.session.undo()?.is_some() {
+            self.invalidate();
+        }
+
+        Ok(())
+    }
+
+    pub fn redo(&mut self) -> Result<(), GameError> {
+        if self.session.redo()?.is_some() {
+            self.invalidate();
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn editing(&self) -> Option<BoardPosition> {
+        self.editing
+    }
+
+    #[inline]
+    pub fn set_editing(&mut self, pos: Option<BoardPosition>) {
+        self.editing = pos;
+    }
+
+    /// Drop everything worked out for the previous board or settings
+    fn invalidate(&mut self) {
+        self.editing = None;
+        self.clear_previews();
+        self.game_value =
+            GameValue::from_board_with(self.session.board(), self.session.payouts()).ok();
+    }
+
+    #[inline]
+    fn clear_previews(&mut self) {
+        self.previews = Default::default();
+    }
+
+    /// Active MGP bonus percentage
+    #[inline]
+    pub fn bonus(&self) -> u16 {
+        self.session.payouts().bonus()
+    }
+
+    pub fn set_bonus(&mut self, bonus: u16) {
+        let payouts = self.session.payouts().with_bonus(bonus);
+        self.session.set_payouts(payouts);
+        self.invalidate();
+    }
+
+    #[inline]
+    pub fn objective(&self) -> Objective {
+        self.session.objective()
+    }
+
+    pub fn set_objective(&mut self, objective: Objective) {
+        self.session.set_objective(objective);
+        self.clear_previews();
     }
 }